@@ -0,0 +1,51 @@
+// アニメーションデータからフレームデータ表を出力する
+// frame_data <animation.ron> [csv|markdown|json] [hit_level.hit.ron]
+use amethyst_sprite_studio::resource::data::AnimationData;
+use fight_game::{
+    paramater::FightTranslation, resource::hit_level::HitLevelTable,
+    types::frame_data::FrameDataTable,
+};
+use serde::de::DeserializeOwned;
+use std::{env, fs, process};
+
+fn read_ron<T: DeserializeOwned>(path: &str) -> T {
+    let source = fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("read error: {}: {}", path, err);
+        process::exit(1);
+    });
+    ron::de::from_str(&source).unwrap_or_else(|err| {
+        eprintln!("parse error: {}: {}", path, err);
+        process::exit(1);
+    })
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let path = match args.get(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: frame_data <animation.ron> [csv|markdown|json] [hit_level.hit.ron]");
+            process::exit(1);
+        }
+    };
+    let format = args.get(2).map(|s| s.as_str()).unwrap_or("markdown");
+
+    let animation: AnimationData<FightTranslation> = read_ron(path);
+    // 設定表を省略した場合はヒットレベルの既定値を使う
+    let hit_levels: Option<HitLevelTable> = args.get(3).map(|path| read_ron(path));
+
+    let table = FrameDataTable::from_animation(&animation, hit_levels.as_ref());
+    let output = match format {
+        "csv" => table.to_csv(),
+        "markdown" | "md" => table.to_markdown(),
+        "json" => table.to_json().unwrap_or_else(|err| {
+            eprintln!("json error: {}", err);
+            process::exit(1);
+        }),
+        _ => {
+            eprintln!("unknown format: {}", format);
+            process::exit(1);
+        }
+    };
+    print!("{}", output);
+}
@@ -1,7 +1,13 @@
+mod ai;
 mod collision;
 mod paramater;
+mod projectile;
+mod training;
 mod transform;
 
+pub use ai::FightAiBundle;
 pub use collision::FightCollisionBundle;
 pub use paramater::FightParamaterBundle;
+pub use projectile::FightProjectileBundle;
+pub use training::FightTrainingBundle;
 pub use transform::FightTransformBundle;
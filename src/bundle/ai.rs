@@ -0,0 +1,47 @@
+use crate::{
+    paramater::AnimationParam,
+    resource::ai::{AiInputQueues, AiSetting, AiState},
+    system::ai::AiSystem,
+};
+use amethyst::{
+    core::SystemBundle,
+    ecs::{DispatcherBuilder, World},
+};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// CPU操作を行うバンドル
+// FightParamaterBundle の後に追加する
+// CPUの入力は次のフレームの入力として反映される(反応遅延に含まれる)
+// トレーニングダミーと同じプレイヤーを指定した場合は先に入力元を差し替えた方が操作する
+pub struct FightAiBundle<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> FightAiBundle<T> {
+    pub fn new() -> Self {
+        FightAiBundle {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T> SystemBundle<'a, 'b> for FightAiBundle<T>
+where
+    T: AnimationFile<UserData = AnimationParam>,
+{
+    fn build(
+        self,
+        world: &mut World,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), amethyst::Error> {
+        log::info!("fight ai bundle build");
+
+        world.insert(AiSetting::default());
+        world.insert(AiState::default());
+        world.insert(AiInputQueues::default());
+        builder.add(AiSystem::<T>::new(), "ai_system", &["fight_input_system"]);
+
+        Ok(())
+    }
+}
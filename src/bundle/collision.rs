@@ -1,17 +1,28 @@
 use crate::{
+    resource::{
+        clash::ClashSetting,
+        diagnostic::HitDiagnostic,
+        hit_event::HitEvent,
+        match_state::{MatchEvent, MatchState},
+        trade::TradeRule,
+    },
     system::{
-        apply_hit_info::ApplyHitInfoSystem, damage_judge::DamageJudgeSystem, extrude::ExtrudeSystem,
+        apply_hit_info::ApplyHitInfoSystem, clash::ClashSystem, damage_judge::DamageJudgeSystem,
+        extrude::ExtrudeSystem, hit_shake::HitShakeSystem, match_flow::MatchSystem,
+        trade::TradeSystem,
     },
     traits::{ExtrudeFilter, ParamaterFromData, UpdateHitInfo},
 };
 use amethyst::{
     core::SystemBundle,
     ecs::{DispatcherBuilder, World},
+    shrev::EventChannel,
 };
 use amethyst_sprite_studio::traits::animation_file::AnimationFile;
 use std::marker::PhantomData;
 
 // 判定処理後に行う処理をまとめたバンドル
+// ヒットストップ中の揺れは HitShake::offset に入るだけなので，描画側で加える
 pub struct FightCollisionBundle<T, P, H> {
     _animation_file: PhantomData<T>,
     _paramater: PhantomData<P>,
@@ -40,7 +51,7 @@ where
 {
     fn build(
         self,
-        _world: &mut World,
+        world: &mut World,
         builder: &mut DispatcherBuilder,
     ) -> Result<(), amethyst::Error> {
         log::info!("fight collision bundle build");
@@ -49,13 +60,42 @@ where
         builder.add(ExtrudeSystem::<P>::new(), "extrude_system", &[]);
 
         // 判定で起きたことをパラメータへ書き込み処理
+        // 攻撃同士の相殺は設定で有効にする
+        world.insert(ClashSetting::default());
+        world.insert(EventChannel::<HitDiagnostic>::default());
         builder.add(DamageJudgeSystem::<H>::new(), "damage_judge_system", &[]);
 
+        // 相打ちの解決
+        world.insert(TradeRule::default());
+        builder.add(
+            TradeSystem::<T>::new(),
+            "trade_system",
+            &["damage_judge_system"],
+        );
+
         builder.add_barrier();
 
         // 判定を適用
+        world.insert(EventChannel::<HitEvent<T>>::default());
         builder.add(ApplyHitInfoSystem::<T>::new(), "apply_hit_info", &[]);
 
+        // ラウンド進行(ダメージ適用後に勝敗判定)
+        world.insert(MatchState::default());
+        world.insert(EventChannel::<MatchEvent>::default());
+        builder.add(MatchSystem::<T>::new(), "match_system", &["apply_hit_info"]);
+
+        // ヒットストップ中の揺れ
+        // ゲーム進行の Transform は変えないので，描画用のディスパッチで
+        // 描画用の行列(AnimationNodes を作るときの行列など)に offset を加えて使う
+        builder.add(
+            HitShakeSystem::new(),
+            "hit_shake_system",
+            &["apply_hit_info"],
+        );
+
+        // 相殺後のキャンセル猶予
+        builder.add(ClashSystem::<T>::new(), "clash_system", &["apply_hit_info"]);
+
         Ok(())
     }
 }
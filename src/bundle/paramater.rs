@@ -2,10 +2,23 @@
 use crate::system::debug;
 use crate::{
     input::FightInput,
-    resource::command::CommandList,
+    resource::{
+        character::{CharacterDefinition, CharacterStore},
+        clock::FrameClock,
+        command::CommandList,
+        hit_level::{HitLevelStore, HitLevelTable},
+        input_history::InputHistory,
+        input_source::InputSources,
+        knockdown::KnockdownRule,
+        replay::InputReplay,
+        stage::Stage,
+        training::{TrainingDummyState, TrainingSetting},
+    },
     system::{
-        command_activate::CommandActivateSystem, knockback::KnockbackSystem,
+        command_activate::CommandActivateSystem, down::DownSystem, hitstop::HitstopSystem,
+        input::FightInputSystem, input_history::InputHistorySystem, knockback::KnockbackSystem,
         register_collider::RegisterColliderSystem, skill_count::SkillCountSystem,
+        training::TrainingDummySystem,
     },
     traits::{ExtrudeFilter, ParamaterFromData, UpdateHitInfo},
 };
@@ -13,9 +26,10 @@ use amethyst::{
     assets::Processor,
     core::SystemBundle,
     ecs::{DispatcherBuilder, World},
+    utils::circular_buffer::CircularBuffer,
 };
 use amethyst_sprite_studio::traits::animation_file::AnimationFile;
-use input_handle::{system::InputHandleSystem, traits::InputParser};
+use input_handle::traits::InputParser;
 use std::marker::PhantomData;
 
 // パラメータのセット，登録を行うバンドル
@@ -51,21 +65,67 @@ where
         builder: &mut DispatcherBuilder,
     ) -> Result<(), amethyst::Error> {
         log::info!("fight paramater bundle build");
+
+        // 固定fpsのフレーム管理(進めるのは Simulation)
+        world.insert(FrameClock::default());
+
+        // ヒットストップ(アニメーションの更新より前に実行する)
+        builder.add(HitstopSystem::new(), "hitstop_system", &[]);
+
         builder.add(
             Processor::<CommandList>::new(),
             "command_list_processor",
             &[],
         );
 
+        // キャラクター定義
+        builder.add(
+            Processor::<CharacterDefinition>::new(),
+            "character_definition_processor",
+            &[],
+        );
+        world.insert(CharacterStore::new());
+
+        // ヒットレベルの設定表(読み込むまでは既定値を使う)
+        builder.add(
+            Processor::<HitLevelTable>::new(),
+            "hit_level_table_processor",
+            &[],
+        );
+        world.insert(HitLevelStore::default());
+
         // コマンドのイベントチャンネル登録
         world.insert(amethyst::shrev::EventChannel::<
             <FightInput as InputParser>::Event,
         >::default());
         world.insert(crate::resource::command::CommandStore::new());
+
+        // 入力バッファと入力元，リプレイ管理
+        world.insert(
+            CircularBuffer::<<FightInput as InputParser>::InputSignal>::new(
+                FightInput::BUFFER_SIZE,
+            ),
+        );
+        world.insert(InputSources::default());
+        world.insert(InputReplay::default());
+
+        // トレーニングダミー(有効にするまでは何もしない)
+        world.insert(TrainingSetting::default());
+        world.insert(TrainingDummyState::default());
+        builder.add(TrainingDummySystem::new(), "training_dummy_system", &[]);
+
         builder.add(
-            InputHandleSystem::<FightInput>::new(),
+            FightInputSystem::new(),
             "fight_input_system",
-            &[],
+            &["training_dummy_system"],
+        );
+
+        // 入力履歴(表示用)
+        world.insert(InputHistory::default());
+        builder.add(
+            InputHistorySystem::new(),
+            "input_history_system",
+            &["fight_input_system"],
         );
 
         builder.add(
@@ -78,7 +138,7 @@ where
         builder.add(
             debug::input::InputDebugSystem::new(world),
             "input_debug_system",
-            &[],
+            &["input_history_system"],
         );
 
         #[cfg(feature = "debug")]
@@ -99,7 +159,20 @@ where
         builder.add(SkillCountSystem::<T>::new(), "skill_count_system", &[]);
 
         // ノックバック情報更新
-        builder.add(KnockbackSystem::<T>::new(), "knockback_system", &[]);
+        builder.add(
+            KnockbackSystem::<T>::new(),
+            "knockback_system",
+            &["hitstop_system"],
+        );
+
+        // ダウン，起き上がり
+        world.insert(KnockdownRule::default());
+        world.insert(Stage::default());
+        builder.add(
+            DownSystem::new(),
+            "down_system",
+            &["knockback_system", "fight_input_system"],
+        );
         Ok(())
     }
 }
@@ -0,0 +1,62 @@
+use crate::{
+    id::{
+        file::FileId,
+        pack::{AnimationKey, PackKey},
+    },
+    paramater::AnimationParam,
+    system::projectile::{ProjectileSpawnSystem, ProjectileSystem},
+};
+use amethyst::{
+    core::SystemBundle,
+    ecs::{DispatcherBuilder, World},
+};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// アニメーションデータから弾を生成して動かすバンドル
+// FightParamaterBundle, FightCollisionBundle の後に追加する
+pub struct FightProjectileBundle<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> FightProjectileBundle<T> {
+    pub fn new() -> Self {
+        FightProjectileBundle {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T> SystemBundle<'a, 'b> for FightProjectileBundle<T>
+where
+    T: AnimationFile<
+        FileId = FileId,
+        PackKey = PackKey,
+        AnimationKey = AnimationKey,
+        UserData = AnimationParam,
+    >,
+{
+    fn build(
+        self,
+        _world: &mut World,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), amethyst::Error> {
+        log::info!("fight projectile bundle build");
+
+        // 技の使用回数で生成済みか判定するのでカウント後に実行
+        builder.add(
+            ProjectileSpawnSystem::<T>::new(),
+            "projectile_spawn_system",
+            &["skill_count_system"],
+        );
+
+        // ヒットした弾を消すのでヒット情報の適用後に実行
+        builder.add(
+            ProjectileSystem::<T>::new(),
+            "projectile_system",
+            &["apply_hit_info"],
+        );
+
+        Ok(())
+    }
+}
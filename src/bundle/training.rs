@@ -0,0 +1,46 @@
+use crate::{
+    paramater::AnimationParam, resource::training_hud::TrainingHud,
+    system::training_hud::TrainingHudSystem,
+};
+use amethyst::{
+    core::SystemBundle,
+    ecs::{DispatcherBuilder, World},
+};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// トレーニングモードの表示用情報を集計するバンドル
+// FightCollisionBundle の後に追加する
+pub struct FightTrainingBundle<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> FightTrainingBundle<T> {
+    pub fn new() -> Self {
+        FightTrainingBundle {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T> SystemBundle<'a, 'b> for FightTrainingBundle<T>
+where
+    T: AnimationFile<UserData = AnimationParam> + std::fmt::Debug,
+{
+    fn build(
+        self,
+        world: &mut World,
+        builder: &mut DispatcherBuilder,
+    ) -> Result<(), amethyst::Error> {
+        log::info!("fight training bundle build");
+
+        world.insert(TrainingHud::default());
+        builder.add(
+            TrainingHudSystem::<T>::new(),
+            "training_hud_system",
+            &["apply_hit_info"],
+        );
+
+        Ok(())
+    }
+}
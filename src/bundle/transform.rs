@@ -1,5 +1,6 @@
 use crate::{
-    system::direction::DirectionSystem,
+    id::pack::AnimationKey,
+    system::{direction::DirectionSystem, movement::MovementSystem},
     traits::{ExtrudeFilter, ParamaterFromData, UpdateHitInfo},
 };
 use amethyst::{
@@ -28,7 +29,7 @@ impl<T, P, H> FightTransformBundle<T, P, H> {
 
 impl<'a, 'b, T, P, H> SystemBundle<'a, 'b> for FightTransformBundle<T, P, H>
 where
-    T: AnimationFile + std::fmt::Debug,
+    T: AnimationFile<AnimationKey = AnimationKey> + std::fmt::Debug,
     P: 'static
         + Send
         + Sync
@@ -45,6 +46,13 @@ where
 
         builder.add(DirectionSystem::new(), "direction_system", &[]);
 
+        // キャラクター定義の移動性能で移動する
+        builder.add(
+            MovementSystem::<T>::new(),
+            "movement_system",
+            &["direction_system"],
+        );
+
         Ok(())
     }
 }
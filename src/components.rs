@@ -1,17 +1,39 @@
+mod armor_count;
+mod character_status;
+mod clash_cancel;
 mod command;
 mod damaged;
 mod direction;
+mod down;
+mod gauge;
+mod health;
 mod hit_info;
+mod hit_shake;
+mod hitstop;
+mod jump;
 mod knockback;
 mod player_tag;
+mod projectile;
 mod skill_count;
 mod skill_set;
+mod spawn_history;
 
+pub use armor_count::ArmorCount;
+pub use character_status::CharacterStatus;
+pub use clash_cancel::ClashCancel;
 pub use command::ActiveCommand;
 pub use damaged::Damaged;
 pub use direction::Direction;
+pub use down::{Down, DownState, TechRoll};
+pub use gauge::Gauge;
+pub use health::Health;
 pub use hit_info::HitInfo;
+pub use hit_shake::HitShake;
+pub use hitstop::Hitstop;
+pub use jump::Jump;
 pub use knockback::Knockback;
 pub use player_tag::PlayerTag;
+pub use projectile::Projectile;
 pub use skill_count::SkillCount;
 pub use skill_set::SkillSet;
+pub use spawn_history::SpawnHistory;
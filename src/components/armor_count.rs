@@ -0,0 +1,68 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+
+// 技ごとのアーマーで受け止めた回数
+// 技が変わったら(同じ技の再使用を含む)数えなおす
+pub struct ArmorCount<T>
+where
+    T: AnimationFile,
+{
+    key: Option<(T::FileId, T::PackKey, T::AnimationKey, u64)>,
+    count: u32,
+}
+
+impl<T> ArmorCount<T>
+where
+    T: AnimationFile,
+{
+    pub fn new() -> Self {
+        ArmorCount {
+            key: None,
+            count: 0,
+        }
+    }
+
+    // key は(ファイル, パック, アニメーション, 技の使用回数)
+    pub fn count(&self, key: &(T::FileId, T::PackKey, T::AnimationKey, u64)) -> u32 {
+        match &self.key {
+            Some(current) if current == key => self.count,
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, key: (T::FileId, T::PackKey, T::AnimationKey, u64), count: u32) {
+        self.count = self.count(&key) + count;
+        self.key = Some(key);
+    }
+}
+
+impl<T> Clone for ArmorCount<T>
+where
+    T: AnimationFile,
+{
+    fn clone(&self) -> Self {
+        ArmorCount {
+            key: self.key,
+            count: self.count,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ArmorCount<T>
+where
+    T: AnimationFile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArmorCount")
+            .field("key", &self.key)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+impl<T> Component for ArmorCount<T>
+where
+    T: AnimationFile,
+{
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,36 @@
+use crate::paramater::{JumpParamater, MoveParamater};
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// キャラクター定義から設定される移動性能
+#[derive(Debug, Clone)]
+pub struct CharacterStatus {
+    name: String,
+    movement: MoveParamater,
+    jump: JumpParamater,
+}
+
+impl CharacterStatus {
+    pub(crate) fn new(name: String, movement: MoveParamater, jump: JumpParamater) -> Self {
+        CharacterStatus {
+            name,
+            movement,
+            jump,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn movement(&self) -> &MoveParamater {
+        &self.movement
+    }
+
+    pub fn jump(&self) -> &JumpParamater {
+        &self.jump
+    }
+}
+
+impl Component for CharacterStatus {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,36 @@
+use crate::flag::Cancel;
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// 攻撃同士の相殺後にキャンセルできる残りフレーム数
+#[derive(Debug, Clone)]
+pub struct ClashCancel {
+    rest_frame: usize,
+    cancel: Cancel,
+}
+
+impl ClashCancel {
+    pub(crate) fn new(rest_frame: usize, cancel: Cancel) -> Self {
+        ClashCancel { rest_frame, cancel }
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.rest_frame = self.rest_frame.saturating_sub(1);
+    }
+
+    pub fn rest_frame(&self) -> usize {
+        self.rest_frame
+    }
+
+    // キャンセル可能な行動(時間切れなら空)
+    pub fn cancel(&self) -> Cancel {
+        if self.rest_frame > 0 {
+            self.cancel
+        } else {
+            Cancel::empty()
+        }
+    }
+}
+
+impl Component for ClashCancel {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -2,6 +2,7 @@ use crate::id::command::Command;
 use amethyst::ecs::{Component, DenseVecStorage};
 use std::collections::HashSet;
 
+#[derive(Clone)]
 pub struct ActiveCommand {
     active_commands: HashSet<Command>,
 }
@@ -1,5 +1,5 @@
 use crate::types::DamageCollisionId;
-use amethyst::ecs::{Component, DenseVecStorage};
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
 use amethyst_sprite_studio::traits::animation_file::AnimationFile;
 use std::collections::BTreeSet;
 
@@ -39,10 +39,25 @@ where
         self.damaged_collision_ids.clear();
     }
 
-    #[cfg(feature = "debug")]
     pub(crate) fn damaged_ids(&self) -> impl Iterator<Item = &DamageCollisionId<T>> {
         self.damaged_collision_ids.iter()
     }
+
+    // スナップショットから作り直したエンティティへの参照を付け替える
+    pub(crate) fn remap_entities<F>(&mut self, remap: F)
+    where
+        F: Fn(Entity) -> Entity,
+    {
+        self.damaged_collision_ids = self
+            .damaged_collision_ids
+            .iter()
+            .map(|id| {
+                let mut id = *id;
+                id.remap_owner(&remap);
+                id
+            })
+            .collect();
+    }
 }
 
 impl<T> Component for Damaged<T>
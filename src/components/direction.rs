@@ -6,7 +6,7 @@ use amethyst::{
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Serialize, Deserialize, PrefabData)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, PrefabData)]
 #[prefab(Component)]
 pub enum Direction {
     Right, // コマンド認識のデフォルト向き
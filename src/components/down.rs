@@ -0,0 +1,82 @@
+use crate::paramater::Knockdown;
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// 受け身の方向(向き基準)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TechRoll {
+    Neutral, // その場受け身
+    Forward,
+    Back,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownState {
+    Falling,                                       // ダウンする攻撃のノックバック中
+    Lying { frame: usize },                        // ダウン中(経過フレーム)
+    TechRoll { direction: TechRoll, rest: usize }, // 受け身中
+    WakeUp { rest: usize },                        // 起き上がり中
+}
+
+// ダウン状態
+// ダウンする攻撃を受けてから起き上がるまで付与される
+#[derive(Debug, Clone)]
+pub struct Down {
+    knockdown: Knockdown,
+    state: DownState,
+    pub(crate) ground_bounce: bool, // 着地時にバウンドする(1回のみ)
+    pub(crate) wall_bounce: bool,   // 壁に当たったらバウンドする(1回のみ)
+}
+
+impl Down {
+    pub(crate) fn new(knockdown: Knockdown, ground_bounce: bool, wall_bounce: bool) -> Self {
+        Down {
+            knockdown,
+            state: DownState::Falling,
+            ground_bounce,
+            wall_bounce,
+        }
+    }
+
+    pub fn knockdown(&self) -> Knockdown {
+        self.knockdown
+    }
+
+    pub fn state(&self) -> DownState {
+        self.state
+    }
+
+    pub(crate) fn set_state(&mut self, state: DownState) {
+        self.state = state;
+    }
+
+    // 寝ている間
+    pub fn is_lying(&self) -> bool {
+        match self.state {
+            DownState::Lying { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn tech_roll(&self) -> Option<TechRoll> {
+        match self.state {
+            DownState::TechRoll { direction, .. } => Some(direction),
+            _ => None,
+        }
+    }
+
+    pub fn is_wake_up(&self) -> bool {
+        match self.state {
+            DownState::WakeUp { .. } => true,
+            _ => false,
+        }
+    }
+
+    // 寝ている間，受け身中，起き上がり中は無敵
+    pub fn is_invulnerable(&self) -> bool {
+        self.state != DownState::Falling
+    }
+}
+
+impl Component for Down {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,50 @@
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{Component, DenseVecStorage, Entity, WriteStorage},
+    error::Error,
+};
+use serde::{Deserialize, Serialize};
+
+// 超必殺技などで使用するゲージ
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PrefabData)]
+#[prefab(Component)]
+pub struct Gauge {
+    max: f32,
+    current: f32,
+}
+
+impl Gauge {
+    pub fn new(max: f32) -> Self {
+        Gauge { max, current: 0. }
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    pub fn add(&mut self, value: f32) {
+        self.current = (self.current + value).max(0.).min(self.max);
+    }
+
+    // 足りている場合だけ消費する
+    pub fn consume(&mut self, value: f32) -> bool {
+        if self.current < value {
+            return false;
+        }
+        self.current -= value;
+        true
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+}
+
+impl Component for Gauge {
+    type Storage = DenseVecStorage<Self>;
+}
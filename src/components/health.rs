@@ -0,0 +1,45 @@
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{Component, DenseVecStorage, Entity, WriteStorage},
+    error::Error,
+};
+use serde::{Deserialize, Serialize};
+
+// 体力
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PrefabData)]
+#[prefab(Component)]
+pub struct Health {
+    max: f32,
+    current: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Health { max, current: max }
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.
+    }
+
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+
+    pub(crate) fn damage(&mut self, damage: f32) {
+        self.current = (self.current - damage).max(0.);
+    }
+}
+
+impl Component for Health {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -1,10 +1,24 @@
 use crate::{
-    paramater::{CollisionParamater, CollisionType},
-    traits::{HitType, UpdateHitInfo, UpdateHitInfoType},
-    types::DamageCollisionId,
+    components::{Direction, Down, DownState, Knockback, PlayerTag, Projectile},
+    input::{FightInput, InputFlag, InputSignal},
+    paramater::{BlowInfo, CollisionParamater, CollisionType, HitLevel},
+    resource::{
+        clash::{ClashResult, ClashSetting},
+        hit_level::{HitLevelStore, HitLevelTable},
+    },
+    traits::{HitType, UnexpectedHitType, UpdateHitInfo, UpdateHitInfoType},
+    types::{DamageCollisionId, SparkKind},
+};
+use amethyst::{
+    assets::AssetStorage,
+    core::Transform,
+    ecs::{Component, DenseVecStorage, Entity, Read, ReadExpect, ReadStorage},
+    utils::circular_buffer::CircularBuffer,
 };
-use amethyst::ecs::{Component, DenseVecStorage, Entity};
 use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use input_handle::traits::InputParser;
+
+type Signal<'a> = <FightInput as InputParser<'a>>::InputSignal;
 
 // 攻撃側の押し出し判定以外の接触判定をまとめるコンポーネント
 pub struct HitInfo<T>
@@ -17,6 +31,10 @@ where
     // 攻撃してきた相手
     pub(crate) attack_owner: Option<Entity>,
 
+    // 攻撃してきた判定を持つエンティティ(弾の場合は弾自身)と判定ID
+    pub(crate) attack_entity: Option<Entity>,
+    pub(crate) attack_collision_id: Option<DamageCollisionId<T>>,
+
     // 判定ID(全く同じ攻撃)を受けないために保存
     pub(crate) damage_collision_ids: Vec<DamageCollisionId<T>>,
 
@@ -25,6 +43,49 @@ where
 
     // ノックバックフレーム
     pub(crate) knockback: Option<usize>,
+
+    // 受けたダメージ
+    pub(crate) damage: Option<f32>,
+
+    // 受けた攻撃のヒットレベル
+    pub(crate) hit_level: Option<HitLevel>,
+
+    // 受けた攻撃の硬直情報(ダウン，バウンドの判定に使う)
+    pub(crate) blow: Option<BlowInfo>,
+
+    // 硬直の間に押し返される距離
+    pub(crate) pushback: Option<f32>,
+
+    // アーマーで受け止めた回数
+    pub(crate) armored: u32,
+
+    // 弾同士の相殺で削られた耐久値
+    pub(crate) durability_damage: u32,
+
+    // 弾を跳ね返した相手(跳ね返した判定の持ち主)
+    pub(crate) reflected_by: Option<Entity>,
+
+    // 弾が消された
+    pub(crate) absorbed: bool,
+
+    // 攻撃同士の相殺の結果と相殺した相手
+    pub(crate) clash: Option<ClashResult>,
+    pub(crate) clash_owner: Option<Entity>,
+
+    // 相手と同時に攻撃が当たった
+    pub(crate) trade: bool,
+
+    // カウンターヒットとしてダメージを受けた
+    pub(crate) counter_hit: bool,
+
+    // ガードした(同フレームに受けた攻撃をすべてガードした場合のみ)
+    pub(crate) blocked: bool,
+
+    // 判定同士が重なった範囲の中心
+    pub(crate) contact_point: Option<(f32, f32)>,
+
+    // 攻撃を受けたときの揺れのフレーム数
+    pub(crate) shake: Option<usize>,
 }
 
 impl<T> Component for HitInfo<T>
@@ -42,13 +103,263 @@ where
         HitInfo {
             damaged_owners: Vec::with_capacity(16),
             attack_owner: None,
+            attack_entity: None,
+            attack_collision_id: None,
             damage_collision_ids: Vec::with_capacity(16),
             hitstop: None,
             knockback: None,
+            damage: None,
+            hit_level: None,
+            blow: None,
+            pushback: None,
+            armored: 0,
+            durability_damage: 0,
+            reflected_by: None,
+            absorbed: false,
+            clash: None,
+            clash_owner: None,
+            trade: false,
+            counter_hit: false,
+            blocked: false,
+            contact_point: None,
+            shake: None,
         }
     }
 }
 
+impl<T> Clone for HitInfo<T>
+where
+    T: AnimationFile,
+{
+    fn clone(&self) -> Self {
+        HitInfo {
+            damaged_owners: self.damaged_owners.clone(),
+            attack_owner: self.attack_owner,
+            attack_entity: self.attack_entity,
+            attack_collision_id: self.attack_collision_id,
+            damage_collision_ids: self.damage_collision_ids.clone(),
+            hitstop: self.hitstop,
+            knockback: self.knockback,
+            damage: self.damage,
+            hit_level: self.hit_level,
+            blow: self.blow,
+            pushback: self.pushback,
+            armored: self.armored,
+            durability_damage: self.durability_damage,
+            reflected_by: self.reflected_by,
+            absorbed: self.absorbed,
+            clash: self.clash,
+            clash_owner: self.clash_owner,
+            trade: self.trade,
+            counter_hit: self.counter_hit,
+            blocked: self.blocked,
+            contact_point: self.contact_point,
+            shake: self.shake,
+        }
+    }
+}
+
+impl<T> HitInfo<T>
+where
+    T: AnimationFile,
+{
+    pub fn damaged_owners(&self) -> &[Entity] {
+        &self.damaged_owners
+    }
+
+    pub fn attack_owner(&self) -> Option<Entity> {
+        self.attack_owner
+    }
+
+    pub fn attack_entity(&self) -> Option<Entity> {
+        self.attack_entity
+    }
+
+    pub fn attack_collision_id(&self) -> Option<DamageCollisionId<T>> {
+        self.attack_collision_id
+    }
+
+    pub fn hitstop(&self) -> Option<usize> {
+        self.hitstop
+    }
+
+    pub fn knockback(&self) -> Option<usize> {
+        self.knockback
+    }
+
+    pub fn damage(&self) -> Option<f32> {
+        self.damage
+    }
+
+    pub fn hit_level(&self) -> Option<HitLevel> {
+        self.hit_level
+    }
+
+    pub fn blow(&self) -> Option<BlowInfo> {
+        self.blow
+    }
+
+    pub fn pushback(&self) -> Option<f32> {
+        self.pushback
+    }
+
+    pub fn armored(&self) -> u32 {
+        self.armored
+    }
+
+    pub fn durability_damage(&self) -> u32 {
+        self.durability_damage
+    }
+
+    pub fn reflected_by(&self) -> Option<Entity> {
+        self.reflected_by
+    }
+
+    pub fn absorbed(&self) -> bool {
+        self.absorbed
+    }
+
+    pub fn clash(&self) -> Option<ClashResult> {
+        self.clash
+    }
+
+    pub fn clash_owner(&self) -> Option<Entity> {
+        self.clash_owner
+    }
+
+    pub fn trade(&self) -> bool {
+        self.trade
+    }
+
+    pub fn counter_hit(&self) -> bool {
+        self.counter_hit
+    }
+
+    pub fn blocked(&self) -> bool {
+        self.blocked
+    }
+
+    pub fn contact_point(&self) -> Option<(f32, f32)> {
+        self.contact_point
+    }
+
+    pub fn shake(&self) -> Option<usize> {
+        self.shake
+    }
+
+    // ヒットエフェクトの種類(攻撃を受けておらず相殺もしていなければ None)
+    // 同フレームに相殺と被弾が起きた場合は被弾を優先する
+    pub fn spark(&self) -> Option<SparkKind> {
+        match (self.hit_level, self.clash) {
+            (Some(level), _) => Some(SparkKind::from_hit(level, self.blocked)),
+            (None, Some(_)) => Some(SparkKind::Clash),
+            (None, None) => None,
+        }
+    }
+
+    // 相打ちで負けた攻撃を当たらなかったことにする
+    pub(crate) fn cancel_attack(&mut self, target: Entity) {
+        self.damaged_owners.retain(|e| *e != target);
+    }
+
+    // 相打ちで勝った側が受けたダメージを取り消す
+    // 自分の攻撃によるヒットストップは残す
+    pub(crate) fn cancel_damage(&mut self, hitstop: Option<usize>) {
+        self.attack_owner = None;
+        self.attack_entity = None;
+        self.attack_collision_id = None;
+        self.hitstop = hitstop;
+        self.shake = None;
+        self.knockback = None;
+        self.damage = None;
+        self.hit_level = None;
+        self.blow = None;
+        self.armored = 0;
+    }
+
+    // スナップショットから作り直したエンティティへの参照を付け替える
+    pub(crate) fn remap_entities<F>(&mut self, remap: F)
+    where
+        F: Fn(Entity) -> Entity,
+    {
+        for owner in self.damaged_owners.iter_mut() {
+            *owner = remap(*owner);
+        }
+        self.attack_owner = self.attack_owner.map(&remap);
+        self.attack_entity = self.attack_entity.map(&remap);
+        if let Some(id) = self.attack_collision_id.as_mut() {
+            id.remap_owner(&remap);
+        }
+        for id in self.damage_collision_ids.iter_mut() {
+            id.remap_owner(&remap);
+        }
+        self.reflected_by = self.reflected_by.map(&remap);
+        self.clash_owner = self.clash_owner.map(&remap);
+    }
+}
+
+// ガード，空中ヒットの判定に必要な受けた側の情報
+type DefenderData<'s> = (
+    ReadStorage<'s, PlayerTag>,
+    ReadStorage<'s, Direction>,
+    ReadStorage<'s, Knockback>,
+    ReadStorage<'s, Down>,
+    ReadStorage<'s, Transform>,
+    ReadExpect<'s, CircularBuffer<Signal<'s>>>,
+);
+
+trait Defender {
+    fn is_guarding(&self, e: Entity) -> bool;
+    fn is_airborne(&self, e: Entity) -> bool;
+}
+
+impl<'s> Defender for DefenderData<'s> {
+    // 後ろに入力していればガード
+    // ダウン中，ガード以外のやられ中はガードできない
+    fn is_guarding(&self, e: Entity) -> bool {
+        let (tags, directions, knockbacks, downs, _, buffer) = self;
+        if downs.get(e).is_some() {
+            return false;
+        }
+        let stunned = knockbacks
+            .get(e)
+            .map(|knockback| knockback.is_knockback() && knockback.is_blockstun() == false)
+            .unwrap_or(false);
+        if stunned == true {
+            return false;
+        }
+
+        let tag = match tags.get(e) {
+            Some(tag) => tag,
+            None => return false,
+        };
+        let signal: &InputSignal = match buffer.queue().back().and_then(|signal| signal.get(tag)) {
+            Some(signal) => signal,
+            None => return false,
+        };
+        // 入力は画面基準なので向きに合わせて反転
+        let lever = match directions.get(e).cloned().unwrap_or(Direction::Right) {
+            Direction::Right => signal.is_down_flag(),
+            Direction::Left => signal.is_down_flag().mirrored(),
+        };
+        lever.intersects(InputFlag::LEFT | InputFlag::LEFT_DOWN | InputFlag::LEFT_UP)
+    }
+
+    // 座標が基準位置より上か，ダウンする攻撃で吹き飛んでいる間は空中
+    fn is_airborne(&self, e: Entity) -> bool {
+        let (_, _, _, downs, transforms, _) = self;
+        let falling = downs
+            .get(e)
+            .map(|down| down.state() == DownState::Falling)
+            .unwrap_or(false);
+        falling
+            || transforms
+                .get(e)
+                .map(|t| t.translation().y > 0.)
+                .unwrap_or(false)
+    }
+}
+
 impl<T> UpdateHitInfoType for HitInfo<T>
 where
     T: AnimationFile,
@@ -56,18 +367,41 @@ where
     type Paramater = CollisionParamater<T>;
     type CancelInfo = ();
 
-    fn check_hit_type(param1: &Self::Paramater, param2: &Self::Paramater) -> HitType {
+    fn check_hit_type(
+        param1: &Self::Paramater,
+        param2: &Self::Paramater,
+    ) -> Result<HitType, UnexpectedHitType> {
         match (param1.collision_type, param2.collision_type) {
-            (CollisionType::Damaged, CollisionType::Blow { .. }) => HitType::Damage,
-            (CollisionType::Damaged, CollisionType::Projectile { .. }) => HitType::Damage,
-            (CollisionType::Damaged, CollisionType::Throw) => HitType::Damage,
-            (CollisionType::Blow { .. }, CollisionType::Damaged) => HitType::Attack,
-            (CollisionType::Projectile { .. }, CollisionType::Damaged) => HitType::Attack,
-            (CollisionType::Throw, CollisionType::Damaged) => HitType::Attack,
-            _ => unreachable!(
-                "{:?} vs {:?} hit type undefined",
+            (CollisionType::Damaged, CollisionType::Blow { .. }) => Ok(HitType::Damage),
+            (CollisionType::Damaged, CollisionType::Projectile { .. }) => Ok(HitType::Damage),
+            (CollisionType::Damaged, CollisionType::Throw) => Ok(HitType::Damage),
+            (CollisionType::Blow { .. }, CollisionType::Damaged) => Ok(HitType::Attack),
+            (CollisionType::Projectile { .. }, CollisionType::Damaged) => Ok(HitType::Attack),
+            (CollisionType::Throw, CollisionType::Damaged) => Ok(HitType::Attack),
+            (CollisionType::Blow { .. }, CollisionType::Blow { .. }) => Ok(HitType::Clash),
+            (CollisionType::Projectile { .. }, CollisionType::Projectile { .. }) => {
+                Ok(HitType::Clash)
+            }
+            (CollisionType::Projectile { .. }, CollisionType::Reflect) => Ok(HitType::Clash),
+            (CollisionType::Projectile { .. }, CollisionType::Absorb) => Ok(HitType::Clash),
+            (CollisionType::Reflect, CollisionType::Projectile { .. }) => Ok(HitType::Clash),
+            (CollisionType::Absorb, CollisionType::Projectile { .. }) => Ok(HitType::Clash),
+            // 押し出しは別の処理で行う
+            (CollisionType::Extrusion, CollisionType::Extrusion) => Ok(HitType::Ignore),
+            // 攻撃しない判定同士
+            (CollisionType::Damaged, CollisionType::Damaged)
+            | (CollisionType::Damaged, CollisionType::Reflect)
+            | (CollisionType::Damaged, CollisionType::Absorb)
+            | (CollisionType::Reflect, CollisionType::Damaged)
+            | (CollisionType::Reflect, CollisionType::Reflect)
+            | (CollisionType::Reflect, CollisionType::Absorb)
+            | (CollisionType::Absorb, CollisionType::Damaged)
+            | (CollisionType::Absorb, CollisionType::Reflect)
+            | (CollisionType::Absorb, CollisionType::Absorb) => Ok(HitType::Ignore),
+            _ => Err(UnexpectedHitType(format!(
+                "{:?} vs {:?}",
                 param1.collision_type, param2.collision_type
-            ),
+            ))),
         }
     }
 }
@@ -76,29 +410,42 @@ impl<'s, T> UpdateHitInfo<'s> for HitInfo<T>
 where
     T: AnimationFile,
 {
-    type SystemData = ();
+    // 相殺時に相手の弾の耐久値，相殺の設定を参照する
+    // ヒットストップ，硬直はヒットレベルの設定表を参照する
+    // ガードの判定に受けた側の向き，やられ状態，入力を参照する
+    type SystemData = (
+        ReadStorage<'s, Projectile>,
+        Read<'s, ClashSetting>,
+        Read<'s, HitLevelStore>,
+        Read<'s, AssetStorage<HitLevelTable>>,
+        DefenderData<'s>,
+    );
 
     // ヒット情報の更新．
     // ダメージの上書きや他ダメージによる攻撃，ダメージのキャンセルのための情報を返す
     fn attack_update(
         &mut self,
         damage_owner: Entity, //
-        CollisionParamater {
-            collision_type: attack_type,
-            collision_id: _attack_collision_id,
-            ..
-        }: &Self::Paramater,
-        _damage_param: &Self::Paramater,
-        (): &Self::SystemData,
+        attack_param: &Self::Paramater,
+        damage_param: &Self::Paramater,
+        (_, _, hit_levels, tables, _): &Self::SystemData,
     ) -> Vec<(Entity, Self::CancelInfo)> {
         log::trace!("update attack: {:?}", damage_owner,);
         let mut _cancels = Vec::with_capacity(16);
+        if self.damaged_owners.contains(&damage_owner) == false {
+            self.damaged_owners.push(damage_owner);
+        }
+        if self.contact_point.is_none() {
+            self.contact_point = Some(attack_param.contact_point(damage_param));
+        }
 
-        match attack_type {
-            CollisionType::Blow { hit_level, .. } | CollisionType::Projectile { hit_level, .. } => {
-                let hitstop = hit_level.hitstop();
-                self.hitstop = hitstop.into();
+        match attack_param.collision_type {
+            CollisionType::Blow { hit_level, .. } => {
+                // 同フレームに複数ヒットした場合は長い方
+                let hitstop = hit_levels.entry(tables, hit_level).attacker_hitstop();
+                self.hitstop = self.hitstop.max(Some(hitstop));
             }
+            // 弾は当たっても止まらない
             _ => {}
         }
 
@@ -108,34 +455,123 @@ where
     fn damage_update(
         &mut self,
         attack_owner: Entity,
-        CollisionParamater {
-            collision_type: attack_type,
-            collision_id: attack_collision_id,
-            ..
-        }: &Self::Paramater,
-        _damage_param: &Self::Paramater,
-        (): &Self::SystemData,
+        attack_param: &Self::Paramater,
+        damage_param: &Self::Paramater,
+        (_, _, hit_levels, tables, defender): &Self::SystemData,
     ) -> Vec<(Entity, Self::CancelInfo)> {
         log::trace!("update damage: {:?}", attack_owner);
         let mut _cancels = Vec::with_capacity(16);
+        let CollisionParamater {
+            collision_type: attack_type,
+            owner,
+            collision_id: attack_collision_id,
+            ..
+        } = attack_param;
+
+        // 同じ攻撃判定が複数のやられ判定に重なっても1回だけ数える
+        let already = attack_collision_id
+            .map(|id| self.damage_collision_ids.contains(&id))
+            .unwrap_or(false);
+        if already == true {
+            return _cancels;
+        }
+
+        let contact_point = attack_param.contact_point(damage_param);
         match attack_type {
             CollisionType::Blow {
-                hit_level, ground, ..
+                damage,
+                hit_level,
+                ground,
+                air,
+                guard,
+                ..
             }
             | CollisionType::Projectile {
-                hit_level, ground, ..
+                damage,
+                hit_level,
+                ground,
+                air,
+                guard,
+                ..
             } => {
-                let hitstop = hit_level.hitstop();
-                self.hitstop = hitstop.into();
-                self.knockback = ground.frame.into();
+                let entry = hit_levels.entry(tables, *hit_level);
+                let hitstop = entry.defender_hitstop;
+
+                // ガード時の硬直は攻撃側になければ設定表の値を使う
+                // どちらにもない攻撃はガードできない
+                let guard = entry
+                    .blockstun_frame(guard.map(|guard| guard.frame))
+                    .map(|frame| match guard {
+                        Some(guard) => BlowInfo { frame, ..*guard },
+                        None => BlowInfo::from_frame(frame),
+                    })
+                    .filter(|_| defender.is_guarding(damage_param.owner));
+                let blocked = guard.is_some();
+                let (damage, blow) = match guard {
+                    Some(guard) => (0., guard),
+                    None if defender.is_airborne(damage_param.owner) => (*damage, *air),
+                    None => (*damage, *ground),
+                };
+                // 攻撃側で硬直を0にした場合は設定表の値を使う
+                let hitstun = if blocked == true {
+                    blow.frame
+                } else {
+                    entry.hitstun_frame(blow.frame)
+                };
+                self.hitstop = self.hitstop.max(Some(hitstop));
+                self.shake = self.shake.max(Some(hitstop));
+                // 同フレームに複数ヒットした場合は合算
+                self.damage = Some(self.damage.unwrap_or(0.) + damage);
+
+                // 攻撃してきた相手，硬直は最も強い攻撃のものを使う
+                // ガードできなかった攻撃はガードした攻撃より優先する
+                // 接触の順番によらず同じ結果にする
+                let stronger = match self.hit_level {
+                    Some(level) => {
+                        (self.blocked && blocked == false)
+                            || (self.blocked == blocked
+                                && (*hit_level, hitstun) > (level, self.knockback.unwrap_or(0)))
+                    }
+                    None => true,
+                };
+                self.blocked = match self.hit_level {
+                    Some(_) => self.blocked && blocked,
+                    None => blocked,
+                };
+                if stronger == true {
+                    // 弾の場合は生成元を攻撃してきた相手とする
+                    self.attack_owner = Some(*owner);
+                    self.attack_entity = Some(attack_owner);
+                    self.attack_collision_id = *attack_collision_id;
+                    self.contact_point = Some(contact_point);
+                    self.hit_level = Some(*hit_level);
+                }
+
+                if blocked == false && damage_param.is_armored(attack_type, self.armored) == true {
+                    // アーマーで受け止めたらのけぞらない
+                    self.armored += 1;
+                    log::debug!("armored: {} hit", self.armored);
+                } else if stronger == true || self.blow.is_none() {
+                    self.knockback = hitstun.into();
+                    self.blow = Some(blow);
+                    self.pushback = Some(entry.pushback);
+                }
                 log::debug!(
-                    "hitstop = {}, knockback = {}, id = {:?}",
+                    "hitstop = {}, knockback = {}, blocked = {}, id = {:?}",
                     hitstop,
-                    ground.frame,
+                    hitstun,
+                    blocked,
                     attack_collision_id
                 );
             }
-            _ => {}
+            _ => {
+                if self.attack_owner.is_none() {
+                    self.attack_owner = Some(*owner);
+                    self.attack_entity = Some(attack_owner);
+                    self.attack_collision_id = *attack_collision_id;
+                    self.contact_point = Some(contact_point);
+                }
+            }
         }
         if let &Some(attack_collision_id) = attack_collision_id {
             log::debug!("add id = {:?}", attack_collision_id);
@@ -145,6 +581,80 @@ where
         _cancels
     }
 
+    fn clash_update(
+        &mut self,
+        other: Entity,
+        param: &Self::Paramater,
+        other_param: &Self::Paramater,
+        (projectiles, clash_setting, _, _, _): &Self::SystemData,
+    ) -> Vec<(Entity, Self::CancelInfo)> {
+        log::trace!("update clash: {:?}", other);
+        let mut _cancels = Vec::with_capacity(16);
+
+        // 同じ判定との接触は1回だけ数える
+        let already = other_param
+            .collision_id
+            .map(|id| self.damage_collision_ids.contains(&id))
+            .unwrap_or(false);
+        if already == true {
+            return _cancels;
+        }
+
+        match (param.collision_type, other_param.collision_type) {
+            (
+                CollisionType::Blow { hit_level, .. },
+                CollisionType::Blow {
+                    hit_level: other_level,
+                    ..
+                },
+            ) => {
+                if clash_setting.enabled == false {
+                    return _cancels;
+                }
+                if self.contact_point.is_none() {
+                    self.contact_point = Some(param.contact_point(other_param));
+                }
+                let result = clash_setting.resolve(hit_level, other_level);
+                self.hitstop = self.hitstop.max(Some(clash_setting.hitstop));
+                self.clash = Some(result);
+                self.clash_owner = Some(other_param.owner);
+                log::debug!("blow clash: {:?} {:?}", other, result);
+
+                // 負けた場合は相手の攻撃が残るので判定IDを保存しない
+                if result == ClashResult::Lose {
+                    return _cancels;
+                }
+            }
+            (CollisionType::Projectile { .. }, CollisionType::Projectile { .. }) => {
+                // 相手の弾の残り耐久値分削られる
+                let durability = projectiles
+                    .get(other)
+                    .map(|projectile| projectile.durability())
+                    .unwrap_or(1);
+                self.durability_damage += durability;
+                log::debug!("projectile clash: {:?} -{}", other, durability);
+            }
+            (CollisionType::Projectile { .. }, CollisionType::Reflect) => {
+                self.reflected_by = Some(other_param.owner);
+                log::debug!("projectile reflected: {:?}", other_param.owner);
+            }
+            (CollisionType::Projectile { .. }, CollisionType::Absorb) => {
+                self.absorbed = true;
+                log::debug!("projectile absorbed: {:?}", other_param.owner);
+            }
+            _ => {}
+        }
+        if self.contact_point.is_none() {
+            self.contact_point = Some(param.contact_point(other_param));
+        }
+
+        if let Some(id) = other_param.collision_id {
+            self.damage_collision_ids.push(id);
+        }
+
+        _cancels
+    }
+
     // 他エンティティの更新時にキャンセルされた場合に呼び出す．
     fn cancel(&mut self, targeted: Entity, _cancel_info: Self::CancelInfo) {
         // 対象に攻撃した，された場合はキャンセル
@@ -158,3 +668,174 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        flag::Invulnerable,
+        id::{
+            file::FileId,
+            pack::{AnimationKey, PackKey},
+        },
+        paramater::{Armor, FightTranslation},
+    };
+    use amethyst::ecs::{Builder, SystemData, World, WorldExt};
+
+    type Data<'s> = <HitInfo<FightTranslation> as UpdateHitInfo<'s>>::SystemData;
+
+    fn blow() -> BlowInfo {
+        BlowInfo {
+            x: 0.,
+            y: 0.,
+            frame: 10,
+            knockdown: None,
+            ground_bounce: false,
+            wall_bounce: false,
+        }
+    }
+
+    fn collision_types() -> Vec<CollisionType> {
+        vec![
+            CollisionType::Extrusion,
+            CollisionType::Blow {
+                damage: 10.,
+                air: blow(),
+                ground: blow(),
+                guard: None,
+                hit_level: HitLevel::Level1,
+                collision_count: 0,
+            },
+            CollisionType::Projectile {
+                damage: 10.,
+                air: blow(),
+                ground: blow(),
+                guard: None,
+                hit_level: HitLevel::Level1,
+                collision_count: 0,
+            },
+            CollisionType::Throw,
+            CollisionType::Damaged,
+            CollisionType::Reflect,
+            CollisionType::Absorb,
+        ]
+    }
+
+    fn paramater(
+        owner: Entity,
+        collision_type: CollisionType,
+    ) -> CollisionParamater<FightTranslation> {
+        CollisionParamater {
+            collision_type,
+            owner,
+            collision_id: None,
+            damaged_collision_ids: None,
+            invulnerable: Invulnerable::empty(),
+            armor: None,
+            armor_count: 0,
+            position: (0., 0.),
+            size: (10., 10.),
+        }
+    }
+
+    #[test]
+    fn check_hit_type_covers_every_pair() {
+        use crate::traits::HitType::{Attack as A, Clash as C, Damage as D, Ignore as I};
+        const E: Option<HitType> = None;
+
+        // 行が1つ目，列が2つ目の判定(None はエラー)
+        // Extrusion, Blow, Projectile, Throw, Damaged, Reflect, Absorb の順
+        let expected = [
+            [Some(I), E, E, E, E, E, E],
+            [E, Some(C), E, E, Some(A), E, E],
+            [E, E, Some(C), E, Some(A), Some(C), Some(C)],
+            [E, E, E, E, Some(A), E, E],
+            [E, Some(D), Some(D), Some(D), Some(I), Some(I), Some(I)],
+            [E, E, Some(C), E, Some(I), Some(I), Some(I)],
+            [E, E, Some(C), E, Some(I), Some(I), Some(I)],
+        ];
+
+        let mut world = World::new();
+        let e1 = world.create_entity().build();
+        let e2 = world.create_entity().build();
+        let types = collision_types();
+        for (row, &type1) in types.iter().enumerate() {
+            for (column, &type2) in types.iter().enumerate() {
+                let result = HitInfo::<FightTranslation>::check_hit_type(
+                    &paramater(e1, type1),
+                    &paramater(e2, type2),
+                );
+                assert_eq!(
+                    result.ok(),
+                    expected[row][column],
+                    "{:?} vs {:?}",
+                    type1,
+                    type2
+                );
+            }
+        }
+    }
+
+    fn damage_world() -> World {
+        let mut world = World::new();
+        Data::setup(&mut world);
+        world.insert(CircularBuffer::<Signal<'static>>::new(
+            FightInput::BUFFER_SIZE,
+        ));
+        world
+    }
+
+    fn attack(owner: Entity, id: u32) -> CollisionParamater<FightTranslation> {
+        let mut param = paramater(owner, collision_types()[1]);
+        param.collision_id = Some(DamageCollisionId::new(&(
+            owner,
+            FileId::Sample,
+            PackKey::Base,
+            AnimationKey::Punch,
+            id,
+            0,
+        )));
+        param
+    }
+
+    #[test]
+    fn one_attack_over_two_hurtboxes_hits_once() {
+        let mut world = damage_world();
+        let attacker = world.create_entity().build();
+        let defender = world.create_entity().build();
+        let attack = attack(attacker, 0);
+        let hurtbox = paramater(defender, CollisionType::Damaged);
+
+        let data = world.system_data::<Data>();
+        let mut hit = HitInfo::<FightTranslation>::default();
+        hit.damage_update(attacker, &attack, &hurtbox, &data);
+        hit.damage_update(attacker, &attack, &hurtbox, &data);
+
+        assert_eq!(hit.damage, Some(10.));
+        assert_eq!(hit.damage_collision_ids.len(), 1);
+    }
+
+    #[test]
+    fn hit_armor_counts_each_attack_once() {
+        let mut world = damage_world();
+        let attacker = world.create_entity().build();
+        let defender = world.create_entity().build();
+        let first = attack(attacker, 0);
+        let second = attack(attacker, 1);
+        let mut hurtbox = paramater(defender, CollisionType::Damaged);
+        hurtbox.armor = Some(Armor::Hit { count: 1 });
+
+        let data = world.system_data::<Data>();
+        let mut hit = HitInfo::<FightTranslation>::default();
+        // 1回分のアーマーは2つのやられ判定に重なった1つの攻撃を受け止める
+        hit.damage_update(attacker, &first, &hurtbox, &data);
+        hit.damage_update(attacker, &first, &hurtbox, &data);
+        assert_eq!(hit.armored, 1);
+        assert_eq!(hit.knockback, None);
+
+        // 別の攻撃は受け止められない
+        hit.damage_update(attacker, &second, &hurtbox, &data);
+        assert_eq!(hit.armored, 1);
+        assert_eq!(hit.knockback, Some(10));
+    }
+}
@@ -0,0 +1,47 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// 攻撃を受けたときのヒットストップ中の揺れ
+// 描画時だけ座標をずらす(ゲーム内の座標，判定は動かさない)
+// 描画側は Transform を書き換えず，描画に使う行列の x に offset を加える
+#[derive(Debug, Clone)]
+pub struct HitShake {
+    rest_frame: usize,
+    total_frame: usize,
+    pub(crate) offset: f32, // 描画時に加える横方向のずらし
+}
+
+impl HitShake {
+    pub(crate) fn new(frame: usize) -> Self {
+        HitShake {
+            rest_frame: frame,
+            total_frame: frame,
+            offset: 0.,
+        }
+    }
+
+    pub fn rest_frame(&self) -> usize {
+        self.rest_frame
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.rest_frame = self.rest_frame.saturating_sub(1);
+    }
+
+    // 残りフレームに応じて弱くなる左右交互のずらし
+    pub(crate) fn next_offset(&self, amplitude: f32) -> f32 {
+        if self.rest_frame == 0 || self.total_frame == 0 {
+            return 0.;
+        }
+        let rate = self.rest_frame as f32 / self.total_frame as f32;
+        let sign = if self.rest_frame % 2 == 0 { 1. } else { -1. };
+        amplitude * rate * sign
+    }
+}
+
+impl Component for HitShake {
+    type Storage = DenseVecStorage<Self>;
+}
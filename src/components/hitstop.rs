@@ -0,0 +1,26 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// ヒットストップの残りフレーム数
+// 付与されている間はアニメーションとフレームで進む処理を止める
+#[derive(Debug, Clone)]
+pub struct Hitstop {
+    rest_frame: usize,
+}
+
+impl Hitstop {
+    pub(crate) fn new(frame: usize) -> Self {
+        Hitstop { rest_frame: frame }
+    }
+
+    pub fn rest_frame(&self) -> usize {
+        self.rest_frame
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.rest_frame = self.rest_frame.saturating_sub(1);
+    }
+}
+
+impl Component for Hitstop {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,42 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// ジャンプ中の移動量
+// 着地後はジャンプの技が終わるまで残して連続でジャンプしないようにする
+#[derive(Debug, Clone)]
+pub struct Jump {
+    velocity: (f32, f32),
+    landed: bool,
+}
+
+impl Jump {
+    pub(crate) fn new(velocity: (f32, f32)) -> Self {
+        Jump {
+            velocity,
+            landed: false,
+        }
+    }
+
+    pub fn velocity(&self) -> (f32, f32) {
+        self.velocity
+    }
+
+    pub fn is_landed(&self) -> bool {
+        self.landed
+    }
+
+    // 1フレーム分進めて移動量を返す
+    pub(crate) fn advance(&mut self, gravity: f32) -> (f32, f32) {
+        let velocity = self.velocity;
+        self.velocity.1 -= gravity;
+        velocity
+    }
+
+    pub(crate) fn land(&mut self) {
+        self.velocity = (0., 0.);
+        self.landed = true;
+    }
+}
+
+impl Component for Jump {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -1,32 +1,63 @@
 use amethyst::ecs::{Component, DenseVecStorage};
 
-// ノックバックの残り時間
+// ノックバックの残りフレーム数
 // ダメージ用のアニメーションはこのアニメーションが終わってからニュートラルに戻る
+#[derive(Clone)]
 pub struct Knockback {
-    rest_time: f32,
+    rest_frame: usize,
+    blockstun: bool, // ガード硬直
+    push: f32,       // 1フレームに押し返される距離
 }
 
 impl Knockback {
     pub(crate) fn new() -> Self {
         Knockback {
-            rest_time: std::f32::MIN,
+            rest_frame: 0,
+            blockstun: false,
+            push: 0.,
         }
     }
 
-    pub(crate) fn set_knockback(&mut self, time: f32) {
-        self.rest_time = time;
+    pub(crate) fn set_knockback(&mut self, frame: usize) {
+        self.rest_frame = frame;
+        self.blockstun = false;
+        self.push = 0.;
     }
 
-    pub(crate) fn decrement(&mut self, time: f32) {
-        self.rest_time -= time;
+    // ガード硬直中は続けてガードできる
+    pub(crate) fn set_blockstun(&mut self, frame: usize) {
+        self.rest_frame = frame;
+        self.blockstun = true;
+        self.push = 0.;
+    }
+
+    // 残りの硬直の間に distance だけ押し返す(符号が押し返す方向)
+    pub(crate) fn set_pushback(&mut self, distance: f32) {
+        self.push = if self.rest_frame > 0 {
+            distance / self.rest_frame as f32
+        } else {
+            0.
+        };
+    }
+
+    pub(crate) fn push(&self) -> f32 {
+        self.push
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.rest_frame = self.rest_frame.saturating_sub(1);
     }
 
     pub(crate) fn is_knockback(&self) -> bool {
-        self.rest_time > 0.
+        self.rest_frame > 0
+    }
+
+    pub(crate) fn knockback_frame(&self) -> usize {
+        self.rest_frame
     }
 
-    pub(crate) fn knockback_time(&self) -> f32 {
-        self.rest_time
+    pub(crate) fn is_blockstun(&self) -> bool {
+        self.is_knockback() && self.blockstun
     }
 }
 
@@ -0,0 +1,106 @@
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+
+// アニメーションデータから生成された弾
+// 生成元のキャラクターとは独立したエンティティとして動く
+#[derive(Debug, Clone)]
+pub struct Projectile {
+    owner: Entity,
+    spawner: Entity,      // 生成したエンティティ(跳ね返されても変わらない)
+    spawned: (u64, u32),  // 生成したフレームと生成ID
+    velocity: (f32, f32), // 1フレームの移動量(画面基準)
+    rest_frame: usize,
+    rest_hit: u32,
+    durability: u32, // 弾同士の相殺で削られる
+}
+
+impl Projectile {
+    pub(crate) fn new(
+        owner: Entity,
+        spawned: (u64, u32),
+        velocity: (f32, f32),
+        lifetime: usize,
+        hit_count: u32,
+        durability: u32,
+    ) -> Self {
+        Projectile {
+            owner,
+            spawner: owner,
+            spawned,
+            velocity,
+            rest_frame: lifetime,
+            rest_hit: hit_count,
+            durability,
+        }
+    }
+
+    // 弾を生成したエンティティ
+    pub fn owner(&self) -> Entity {
+        self.owner
+    }
+
+    pub fn spawner(&self) -> Entity {
+        self.spawner
+    }
+
+    // ロールバックで作り直してもエンティティによらず弾を識別できる
+    pub fn spawned(&self) -> (u64, u32) {
+        self.spawned
+    }
+
+    pub fn velocity(&self) -> (f32, f32) {
+        self.velocity
+    }
+
+    pub fn rest_frame(&self) -> usize {
+        self.rest_frame
+    }
+
+    pub fn rest_hit(&self) -> u32 {
+        self.rest_hit
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.rest_frame = self.rest_frame.saturating_sub(1);
+    }
+
+    pub fn durability(&self) -> u32 {
+        self.durability
+    }
+
+    pub(crate) fn hit(&mut self, count: u32) {
+        self.rest_hit = self.rest_hit.saturating_sub(count);
+    }
+
+    // 相手の弾の耐久値分削る
+    pub(crate) fn clash(&mut self, damage: u32) {
+        self.durability = self.durability.saturating_sub(damage);
+    }
+
+    // 吸収されたら消える
+    pub(crate) fn vanish(&mut self) {
+        self.rest_hit = 0;
+    }
+
+    // 跳ね返されたら持ち主が変わり逆向きに進む
+    pub(crate) fn reflect(&mut self, owner: Entity) {
+        self.owner = owner;
+        self.velocity.0 *= -1.;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.rest_frame > 0 && self.rest_hit > 0 && self.durability > 0
+    }
+
+    // スナップショットから作り直したエンティティへの参照を付け替える
+    pub(crate) fn remap_entities<F>(&mut self, remap: F)
+    where
+        F: Fn(Entity) -> Entity,
+    {
+        self.owner = remap(self.owner);
+        self.spawner = remap(self.spawner);
+    }
+}
+
+impl Component for Projectile {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -28,6 +28,23 @@ where
     pub fn skill_count(&self, key: &(T::FileId, T::PackKey, T::AnimationKey)) -> u64 {
         self.counts.get(key).map(|count| *count).unwrap_or(0)
     }
+
+    pub(crate) fn counts(
+        &self,
+    ) -> impl Iterator<Item = (&(T::FileId, T::PackKey, T::AnimationKey), &u64)> {
+        self.counts.iter()
+    }
+}
+
+impl<T> Clone for SkillCount<T>
+where
+    T: AnimationFile,
+{
+    fn clone(&self) -> Self {
+        SkillCount {
+            counts: self.counts.clone(),
+        }
+    }
 }
 
 impl<T> Component for SkillCount<T>
@@ -0,0 +1,71 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+
+// 現在の技で生成した弾のID
+// 技が変わったら(同じ技の再使用を含む)クリアする
+pub struct SpawnHistory<T>
+where
+    T: AnimationFile,
+{
+    key: Option<(T::FileId, T::PackKey, T::AnimationKey, u64)>,
+    ids: Vec<u32>,
+}
+
+impl<T> SpawnHistory<T>
+where
+    T: AnimationFile,
+{
+    pub fn new() -> Self {
+        SpawnHistory {
+            key: None,
+            ids: vec![],
+        }
+    }
+
+    // key は(ファイル, パック, アニメーション, 技の使用回数)
+    pub fn is_spawned(&self, key: &(T::FileId, T::PackKey, T::AnimationKey, u64), id: u32) -> bool {
+        match &self.key {
+            Some(current) if current == key => self.ids.contains(&id),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn add(&mut self, key: (T::FileId, T::PackKey, T::AnimationKey, u64), id: u32) {
+        if self.key != Some(key) {
+            self.key = Some(key);
+            self.ids.clear();
+        }
+        self.ids.push(id);
+    }
+}
+
+impl<T> Clone for SpawnHistory<T>
+where
+    T: AnimationFile,
+{
+    fn clone(&self) -> Self {
+        SpawnHistory {
+            key: self.key,
+            ids: self.ids.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for SpawnHistory<T>
+where
+    T: AnimationFile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnHistory")
+            .field("key", &self.key)
+            .field("ids", &self.ids)
+            .finish()
+    }
+}
+
+impl<T> Component for SpawnHistory<T>
+where
+    T: AnimationFile,
+{
+    type Storage = DenseVecStorage<Self>;
+}
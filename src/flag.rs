@@ -1,5 +1,7 @@
 mod cancel;
 mod condition;
+mod invulnerable;
 
 pub use cancel::Cancel;
 pub use condition::Condition;
+pub use invulnerable::Invulnerable;
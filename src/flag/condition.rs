@@ -11,6 +11,9 @@ bitflags::bitflags! {
     pub struct Condition : u64 {
         const KNOCKBACK = 1 << 0;   // ノックバック中
         const AIR = 1 << 1;         // 空中
+        const KNOCKDOWN = 1 << 2;   // ダウン中
+        const TECH_ROLL = 1 << 3;   // 受け身中
+        const WAKE_UP = 1 << 4;     // 起き上がり中
     }
 }
 
@@ -30,12 +33,18 @@ struct ConditionVisitor;
 enum ConditionValue {
     Knockback,
     Air,
+    Knockdown,
+    TechRoll,
+    WakeUp,
 }
 
 // シリアライズ用フラグ優先順位
-const SERIALIZE_FLAGS: [(Condition, ConditionValue); 2] = [
+const SERIALIZE_FLAGS: [(Condition, ConditionValue); 5] = [
     (Condition::KNOCKBACK, ConditionValue::Knockback),
     (Condition::AIR, ConditionValue::Air),
+    (Condition::KNOCKDOWN, ConditionValue::Knockdown),
+    (Condition::TECH_ROLL, ConditionValue::TechRoll),
+    (Condition::WAKE_UP, ConditionValue::WakeUp),
 ];
 
 impl ConditionValue {
@@ -43,6 +52,9 @@ impl ConditionValue {
         match self {
             ConditionValue::Knockback => Condition::KNOCKBACK,
             ConditionValue::Air => Condition::AIR,
+            ConditionValue::Knockdown => Condition::KNOCKDOWN,
+            ConditionValue::TechRoll => Condition::TECH_ROLL,
+            ConditionValue::WakeUp => Condition::WAKE_UP,
         }
     }
 
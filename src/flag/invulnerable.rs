@@ -0,0 +1,115 @@
+use serde::{
+    de::{SeqAccess, Visitor},
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
+
+bitflags::bitflags! {
+    // 無敵フラグ
+    #[derive(Default)]
+    pub struct Invulnerable : u64 {
+        const STRIKE = 1 << 0;      // 打撃無敵
+        const THROW = 1 << 1;       // 投げ無敵
+        const PROJECTILE = 1 << 2;  // 弾無敵
+
+        // 設定用エイリアス
+        const FULL = Self::STRIKE.bits | Self::THROW.bits | Self::PROJECTILE.bits; // 完全無敵
+    }
+}
+
+impl<'de> Deserialize<'de> for Invulnerable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(InvulnerableVisitor)
+    }
+}
+
+struct InvulnerableVisitor;
+
+// 無敵フラグをデシリアライズする用のenum
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+enum InvulnerableValue {
+    Strike,
+    Throw,
+    Projectile,
+
+    // 設定用エイリアス
+    Full,
+}
+
+// シリアライズ用フラグ優先順位
+const SERIALIZE_FLAGS: [(Invulnerable, InvulnerableValue); 4] = [
+    // 設定用エイリアス
+    (Invulnerable::FULL, InvulnerableValue::Full),
+    // 通常フラグ
+    (Invulnerable::STRIKE, InvulnerableValue::Strike),
+    (Invulnerable::THROW, InvulnerableValue::Throw),
+    (Invulnerable::PROJECTILE, InvulnerableValue::Projectile),
+];
+
+impl InvulnerableValue {
+    fn convert_flag(self) -> Invulnerable {
+        match self {
+            InvulnerableValue::Strike => Invulnerable::STRIKE,
+            InvulnerableValue::Throw => Invulnerable::THROW,
+            InvulnerableValue::Projectile => Invulnerable::PROJECTILE,
+
+            // 設定用エイリアス
+            InvulnerableValue::Full => Invulnerable::FULL,
+        }
+    }
+
+    fn from_flag(mut flag: Invulnerable) -> Vec<Self> {
+        SERIALIZE_FLAGS
+            .iter()
+            .filter_map(|&(f, v)| {
+                let val = if flag.contains(f) == true {
+                    Some(v)
+                } else {
+                    None
+                };
+
+                flag.remove(f);
+
+                val
+            })
+            .collect()
+    }
+}
+
+impl<'de> Visitor<'de> for InvulnerableVisitor {
+    type Value = Invulnerable;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("not supported format")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut invulnerable = Invulnerable::empty();
+        while let Some(elem) = seq.next_element()? {
+            let flag = InvulnerableValue::convert_flag(elem);
+            invulnerable |= flag;
+        }
+        Ok(invulnerable)
+    }
+}
+
+impl Serialize for Invulnerable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values = InvulnerableValue::from_flag(*self);
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for v in values {
+            seq.serialize_element(&v)?;
+        }
+        seq.end()
+    }
+}
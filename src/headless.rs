@@ -0,0 +1,216 @@
+use crate::{
+    bundle::{FightCollisionBundle, FightParamaterBundle, FightTransformBundle},
+    components::{Down, DownState, HitInfo, Knockback, PlayerTag},
+    id::pack::AnimationKey,
+    input::{InputFlag, ScriptedSource},
+    resource::{
+        clock::FrameClock,
+        input_source::InputSources,
+        training_hud::{PlayerHud, TrainingHud},
+    },
+    simulation::{Simulation, SimulationBuilder},
+    traits::{ExtrudeFilter, InputSource, ParamaterFromData, UpdateHitInfo},
+};
+use amethyst::{
+    assets::Loader,
+    core::{ArcThreadPool, SystemBundle, Time, Transform},
+    ecs::{Entity, World, WorldExt},
+};
+use amethyst_sprite_studio::{components::PlayAnimationKey, traits::animation_file::AnimationFile};
+use std::sync::Arc;
+
+// テスト用の固定fps
+pub const HEADLESS_FPS: f32 = 60.;
+
+// 描画を行わない戦闘シミュレーションを構築する
+// 判定処理(amethyst-aabb)やアニメーション(amethyst-sprite-studio)のバンドルは
+// with_bundle で追加する
+pub struct HeadlessBuilder<'a, 'b> {
+    world: World,
+    builder: SimulationBuilder<'a, 'b>,
+}
+
+impl<'a, 'b> HeadlessBuilder<'a, 'b> {
+    pub fn new() -> amethyst::Result<Self> {
+        let mut world = World::new();
+
+        let pool: ArcThreadPool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .map_err(|err| amethyst::Error::from_string(format!("{}", err)))?,
+        );
+        world.insert(pool.clone());
+
+        let mut time = Time::default();
+        time.set_delta_seconds(1. / HEADLESS_FPS);
+        world.insert(time);
+
+        Ok(HeadlessBuilder {
+            world,
+            builder: SimulationBuilder::new().with_pool(pool),
+        })
+    }
+
+    // アセット読み込み用のローダーを登録する
+    pub fn with_loader<D>(mut self, asset_dir: D) -> Self
+    where
+        D: Into<String>,
+    {
+        let pool = (*self.world.read_resource::<ArcThreadPool>()).clone();
+        self.world.insert(Loader::new(asset_dir.into(), pool));
+        self
+    }
+
+    pub fn with_bundle<B>(mut self, bundle: B) -> amethyst::Result<Self>
+    where
+        B: SystemBundle<'a, 'b>,
+    {
+        let HeadlessBuilder { mut world, builder } = self;
+        let builder = builder.with_bundle(&mut world, bundle)?;
+        Ok(HeadlessBuilder { world, builder })
+    }
+
+    // 格ゲー用のバンドルをまとめて登録する
+    pub fn with_fight_bundles<T, P, H>(self) -> amethyst::Result<Self>
+    where
+        T: AnimationFile<AnimationKey = AnimationKey> + std::fmt::Debug,
+        P: 'static
+            + Send
+            + Sync
+            + for<'c> ParamaterFromData<'c, T::UserData>
+            + for<'c> ExtrudeFilter<'c>,
+        H: for<'c> UpdateHitInfo<'c, Paramater = P>,
+    {
+        self.with_bundle(FightParamaterBundle::<T, P, H>::new())?
+            .with_bundle(FightCollisionBundle::<T, P, H>::new())?
+            .with_bundle(FightTransformBundle::<T, P, H>::new())
+    }
+
+    pub fn build(self) -> HeadlessWorld<'a, 'b> {
+        let HeadlessBuilder { mut world, builder } = self;
+        let simulation = builder.build(&mut world);
+
+        HeadlessWorld { world, simulation }
+    }
+}
+
+// 1フレームずつ進めて状態を確認するためのワールド
+pub struct HeadlessWorld<'a, 'b> {
+    world: World,
+    simulation: Simulation<'a, 'b>,
+}
+
+impl<'a, 'b> HeadlessWorld<'a, 'b> {
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    // ロールバックなどワールドとディスパッチャを直接操作する場合に使う
+    pub fn split_mut(&mut self) -> (&mut World, &mut Simulation<'a, 'b>) {
+        (&mut self.world, &mut self.simulation)
+    }
+
+    pub fn set_source<S>(&mut self, tag: PlayerTag, source: S)
+    where
+        S: InputSource,
+    {
+        self.world
+            .write_resource::<InputSources>()
+            .set_source(tag, source);
+    }
+
+    // 1フレーム毎の入力をスクリプトとして設定する
+    pub fn script_inputs(&mut self, tag: PlayerTag, inputs: Vec<InputFlag>) {
+        self.set_source(tag, ScriptedSource::new(inputs));
+    }
+
+    // 固定fpsで1フレーム進める
+    pub fn step(&mut self) {
+        {
+            let mut time = self.world.write_resource::<Time>();
+            time.set_delta_seconds(1. / HEADLESS_FPS);
+            time.increment_frame_number();
+        }
+        self.simulation.step(&mut self.world);
+    }
+
+    pub fn run(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    // 条件を満たすまで進める(最大フレーム数まで)
+    // 条件を満たしたらtrue
+    pub fn run_until<F>(&mut self, max_frames: usize, mut predicate: F) -> bool
+    where
+        F: FnMut(&World) -> bool,
+    {
+        for _ in 0..max_frames {
+            if predicate(&self.world) == true {
+                return true;
+            }
+            self.step();
+        }
+        predicate(&self.world)
+    }
+
+    // 固定fpsでの進行フレーム数
+    pub fn frame(&self) -> u64 {
+        self.world.read_resource::<FrameClock>().frame()
+    }
+
+    pub fn position(&self, e: Entity) -> Option<(f32, f32)> {
+        let transforms = self.world.read_storage::<Transform>();
+        let translation = transforms.get(e)?.translation();
+        Some((translation.x, translation.y))
+    }
+
+    pub fn is_knockback(&self, e: Entity) -> bool {
+        self.world
+            .read_storage::<Knockback>()
+            .get(e)
+            .map(|k| k.is_knockback())
+            .unwrap_or(false)
+    }
+
+    pub fn knockback_frame(&self, e: Entity) -> Option<usize> {
+        self.world
+            .read_storage::<Knockback>()
+            .get(e)
+            .map(|k| k.knockback_frame())
+    }
+
+    // ダウン状態(ダウンしていなければ None)
+    pub fn down_state(&self, e: Entity) -> Option<DownState> {
+        self.world.read_storage::<Down>().get(e).map(|d| d.state())
+    }
+
+    // 直前のフレームで発生したヒット情報
+    pub fn with_hit_info<T, F, R>(&self, e: Entity, f: F) -> Option<R>
+    where
+        T: AnimationFile,
+        F: FnOnce(&HitInfo<T>) -> R,
+    {
+        self.world.read_storage::<HitInfo<T>>().get(e).map(f)
+    }
+
+    // トレーニング表示用の情報(FightTrainingBundle 追加時のみ)
+    pub fn training_hud(&self, tag: PlayerTag) -> Option<PlayerHud> {
+        self.world.try_fetch::<TrainingHud>()?.player(tag).cloned()
+    }
+
+    pub fn animation_key<T>(&self, e: Entity) -> Option<(T::FileId, T::PackKey, T::AnimationKey)>
+    where
+        T: AnimationFile,
+    {
+        let keys = self.world.read_storage::<PlayAnimationKey<T>>();
+        let (&file, &pack, &anim) = keys.get(e)?.play_key()?;
+        Some((file, pack, anim))
+    }
+}
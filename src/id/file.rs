@@ -1,5 +1,26 @@
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+// アニメーションファイル
+// キャラクター定義からはこの値で参照する
+// AnimationFile::to_file_name が &'static str を返すのでファイルの一覧はコードで持つ
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum FileId {
     Sample,
     Sandbox,
 }
+
+impl FileId {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FileId::Sample => "sample",
+            FileId::Sandbox => "sandbox",
+        }
+    }
+
+    pub fn sprite_sheet_num(&self) -> usize {
+        match self {
+            FileId::Sample => 1,
+            FileId::Sandbox => 1,
+        }
+    }
+}
@@ -13,10 +13,29 @@ pub enum Error {
     UnknownAnimationName(String),
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum PackKey {
-    Base,
-    TestPack,
+// キーの列挙と全キーの一覧(データ解析などで一覧する用)を同時に定義する
+// 一覧は列挙子から作るので，キーを追加しても一覧の更新漏れが起きない
+macro_rules! keys {
+    ($(#[$meta:meta])* pub enum $name:ident { $($variant:ident,)* }) => {
+        $(#[$meta])*
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            pub fn all() -> &'static [$name] {
+                &[$($name::$variant,)*]
+            }
+        }
+    };
+}
+
+keys! {
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
+    pub enum PackKey {
+        Base,
+        TestPack,
+    }
 }
 
 #[cfg(feature = "serialize")]
@@ -31,20 +50,22 @@ impl FromStr for PackKey {
     }
 }
 
-#[allow(dead_code)]
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum AnimationKey {
-    //
-    BasePose,
-    Punch,
-    Stance,
-    StartUp,
-    Walk,
-    Back,
-    Knockback,
-    KnockbackLoop,
-    //
-    Deform,
+keys! {
+    #[allow(dead_code)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
+    pub enum AnimationKey {
+        //
+        BasePose,
+        Punch,
+        Stance,
+        StartUp,
+        Walk,
+        Back,
+        Knockback,
+        KnockbackLoop,
+        //
+        Deform,
+    }
 }
 
 #[cfg(feature = "serialize")]
@@ -1,5 +1,6 @@
 mod flag;
 mod signal;
+mod source;
 
 use crate::{
     binding::{Action, Axis, FightBindings},
@@ -19,6 +20,7 @@ use std::collections::BTreeMap;
 
 pub use flag::InputFlag;
 pub use signal::InputSignal;
+pub use source::{DeviceSource, InputQueue, QueueSource, ReplaySource, ScriptedSource};
 
 pub struct FightInput;
 
@@ -45,13 +47,11 @@ impl<'s> InputParser<'s> for FightInput {
         let mut signal = BTreeMap::default();
 
         let player1 = make_signal(
-            handler,
-            PlayerTag::P1,
+            device_input(handler, PlayerTag::P1),
             prev_input.and_then(|s| s.get(&PlayerTag::P1)),
         );
         let player2 = make_signal(
-            handler,
-            PlayerTag::P2,
+            device_input(handler, PlayerTag::P2),
             prev_input.and_then(|s| s.get(&PlayerTag::P2)),
         );
 
@@ -181,24 +181,24 @@ fn convert_command_input(signal: &InputSignal, direction: &Direction) -> Key {
     key
 }
 
-fn make_signal(
+// デバイスの入力から押下中のフラグを生成する
+pub(crate) fn device_input(
     handler: &InputHandler<<FightInput as InputParser>::BindingTypes>,
     tag: PlayerTag,
-    prev_input: Option<&InputSignal>,
-) -> InputSignal {
-    let mut signal = InputSignal::default();
+) -> InputFlag {
+    let mut is_down = InputFlag::empty();
 
     if let Some(true) = handler.action_is_down(&Action::A(tag)) {
-        signal.is_down |= InputFlag::A;
+        is_down |= InputFlag::A;
     }
     if let Some(true) = handler.action_is_down(&Action::B(tag)) {
-        signal.is_down |= InputFlag::B;
+        is_down |= InputFlag::B;
     }
     if let Some(true) = handler.action_is_down(&Action::C(tag)) {
-        signal.is_down |= InputFlag::C;
+        is_down |= InputFlag::C;
     }
     if let Some(true) = handler.action_is_down(&Action::D(tag)) {
-        signal.is_down |= InputFlag::D;
+        is_down |= InputFlag::D;
     }
 
     match (
@@ -207,26 +207,34 @@ fn make_signal(
     ) {
         (Some(lr), Some(ud)) => {
             if ud > AXIS_THRESHOLD && lr > AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::RIGHT_UP;
+                is_down |= InputFlag::RIGHT_UP;
             } else if ud > AXIS_THRESHOLD && lr < -AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::LEFT_UP;
+                is_down |= InputFlag::LEFT_UP;
             } else if ud < -AXIS_THRESHOLD && lr > AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::RIGHT_DOWN;
+                is_down |= InputFlag::RIGHT_DOWN;
             } else if ud < -AXIS_THRESHOLD && lr < -AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::LEFT_DOWN;
+                is_down |= InputFlag::LEFT_DOWN;
             } else if lr > AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::RIGHT;
+                is_down |= InputFlag::RIGHT;
             } else if lr < -AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::LEFT;
+                is_down |= InputFlag::LEFT;
             } else if ud > AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::UP;
+                is_down |= InputFlag::UP;
             } else if ud < -AXIS_THRESHOLD {
-                signal.is_down |= InputFlag::DOWN;
+                is_down |= InputFlag::DOWN;
             }
         }
         _ => {}
     }
 
+    is_down
+}
+
+// 押下中のフラグと前フレームの入力から信号を生成する
+pub(crate) fn make_signal(is_down: InputFlag, prev_input: Option<&InputSignal>) -> InputSignal {
+    let mut signal = InputSignal::default();
+    signal.is_down = is_down;
+
     if let Some(prev) = prev_input {
         signal.is_push = signal.is_down & (signal.is_down ^ prev.is_down);
         signal.is_release = !signal.is_down & (signal.is_down ^ prev.is_down);
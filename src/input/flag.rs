@@ -1,3 +1,5 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 bitflags::bitflags! {
     #[derive(Default)]
     pub struct InputFlag : u64{
@@ -18,6 +20,33 @@ bitflags::bitflags! {
     }
 }
 
+impl InputFlag {
+    // 左右を反転した入力
+    pub fn mirrored(self) -> Self {
+        let mut flag = self
+            - (InputFlag::RIGHT
+                | InputFlag::LEFT
+                | InputFlag::RIGHT_DOWN
+                | InputFlag::LEFT_DOWN
+                | InputFlag::RIGHT_UP
+                | InputFlag::LEFT_UP);
+        let pairs = [
+            (InputFlag::RIGHT, InputFlag::LEFT),
+            (InputFlag::RIGHT_DOWN, InputFlag::LEFT_DOWN),
+            (InputFlag::RIGHT_UP, InputFlag::LEFT_UP),
+        ];
+        for &(right, left) in &pairs {
+            if self.contains(right) {
+                flag |= left;
+            }
+            if self.contains(left) {
+                flag |= right;
+            }
+        }
+        flag
+    }
+}
+
 impl std::fmt::Display for InputFlag {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if self.contains(Self::UP) {
@@ -59,3 +88,23 @@ impl std::fmt::Display for InputFlag {
         write!(f, "")
     }
 }
+
+// リプレイ保存用に入力フラグはビット値のままシリアライズする
+impl Serialize for InputFlag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for InputFlag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bits = u64::deserialize(deserializer)?;
+        Ok(InputFlag::from_bits_truncate(bits))
+    }
+}
@@ -1,6 +1,7 @@
 use crate::input::InputFlag;
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InputSignal {
     pub(crate) is_down: InputFlag,
     pub(crate) is_push: InputFlag,
@@ -0,0 +1,145 @@
+use crate::{
+    binding::FightBindings, components::PlayerTag, input::InputFlag, resource::replay::ReplayData,
+    traits::InputSource,
+};
+use amethyst::input::InputHandler;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+// 入力デバイスからの入力
+#[derive(Default)]
+pub struct DeviceSource;
+
+impl InputSource for DeviceSource {
+    fn next_input(&mut self, tag: PlayerTag, handler: &InputHandler<FightBindings>) -> InputFlag {
+        super::device_input(handler, tag)
+    }
+}
+
+// 事前に決めた入力を1フレームずつ返す
+// 最後まで返したら以降は入力なし
+pub struct ScriptedSource {
+    inputs: Vec<InputFlag>,
+    frame: usize,
+}
+
+impl ScriptedSource {
+    pub fn new(inputs: Vec<InputFlag>) -> Self {
+        ScriptedSource { inputs, frame: 0 }
+    }
+
+    // (入力, 継続フレーム数)のリストから作成
+    pub fn from_holds<I>(holds: I) -> Self
+    where
+        I: IntoIterator<Item = (InputFlag, usize)>,
+    {
+        let inputs = holds
+            .into_iter()
+            .flat_map(|(flag, frames)| std::iter::repeat(flag).take(frames))
+            .collect();
+        Self::new(inputs)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.inputs.len()
+    }
+}
+
+impl InputSource for ScriptedSource {
+    fn next_input(&mut self, _: PlayerTag, _: &InputHandler<FightBindings>) -> InputFlag {
+        let input = self
+            .inputs
+            .get(self.frame)
+            .cloned()
+            .unwrap_or(InputFlag::empty());
+        self.frame += 1;
+        input
+    }
+}
+
+// リプレイデータから指定プレイヤーの入力を再生する
+pub struct ReplaySource {
+    source: ScriptedSource,
+}
+
+impl ReplaySource {
+    pub fn new(data: &ReplayData, tag: PlayerTag) -> Self {
+        let inputs = (0..data.frame_count())
+            .filter_map(|f| data.frame(f))
+            .map(|frame| {
+                frame
+                    .get(&tag)
+                    .map(|signal| signal.is_down_flag())
+                    .unwrap_or(InputFlag::empty())
+            })
+            .collect();
+        ReplaySource {
+            source: ScriptedSource::new(inputs),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.source.is_finished()
+    }
+}
+
+impl InputSource for ReplaySource {
+    fn next_input(&mut self, tag: PlayerTag, handler: &InputHandler<FightBindings>) -> InputFlag {
+        self.source.next_input(tag, handler)
+    }
+}
+
+// 外部(AI，通信相手など)から入力を詰めるためのキュー
+// QueueSourceと対で生成し，システムなどからpushする
+#[derive(Clone, Default)]
+pub struct InputQueue {
+    queue: Arc<Mutex<VecDeque<InputFlag>>>,
+}
+
+impl InputQueue {
+    pub fn push(&self, input: InputFlag) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.push_back(input);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.lock().map(|queue| queue.len()).unwrap_or(0)
+    }
+
+    fn pop(&self) -> Option<InputFlag> {
+        self.queue.lock().ok()?.pop_front()
+    }
+}
+
+// InputQueueに詰められた入力を1フレームに1つ取り出す
+// 入力が届いていなければ入力なし
+pub struct QueueSource {
+    queue: InputQueue,
+}
+
+impl QueueSource {
+    pub fn new() -> (Self, InputQueue) {
+        let queue = InputQueue::default();
+        (
+            QueueSource {
+                queue: queue.clone(),
+            },
+            queue,
+        )
+    }
+}
+
+impl InputSource for QueueSource {
+    fn next_input(&mut self, _: PlayerTag, _: &InputHandler<FightBindings>) -> InputFlag {
+        self.queue.pop().unwrap_or(InputFlag::empty())
+    }
+}
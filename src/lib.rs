@@ -2,11 +2,16 @@ pub mod binding;
 pub mod bundle;
 pub mod components;
 pub mod flag;
+pub mod headless;
 pub mod id;
 pub mod input;
 pub mod load;
+pub mod netplay;
 pub mod paramater;
+pub mod prefab;
 pub mod resource;
+pub mod simulation;
+pub mod snapshot;
 mod system;
 pub mod traits;
 pub mod types;
@@ -1,7 +1,11 @@
-use crate::resource::command::{CommandList, CommandStore};
+use crate::resource::{
+    character::{CharacterDefinition, CharacterStore},
+    command::{CommandList, CommandStore},
+    hit_level::{HitLevelStore, HitLevelTable},
+};
 use amethyst::{
     assets::{AssetStorage, Loader, ProgressCounter, RonFormat},
-    ecs::{Read, ReadExpect, World, WriteExpect},
+    ecs::{Read, ReadExpect, World, Write, WriteExpect},
 };
 
 impl CommandLoad for &mut World {
@@ -31,6 +35,54 @@ impl CommandLoad for &mut World {
     }
 }
 
+impl CharacterLoad for &mut World {
+    fn load_character<F, C>(
+        &mut self,
+        dir_path: F, // キャラクター定義ファイルのあるディレクトリパス指定
+        character_name: C,
+        progress: &mut ProgressCounter,
+    ) where
+        F: Into<String>,
+        C: Into<String>,
+    {
+        self.exec(
+            |(mut store, loader, storage): (
+                WriteExpect<CharacterStore>,
+                ReadExpect<Loader>,
+                Read<AssetStorage<CharacterDefinition>>,
+            )| {
+                let dir_path = dir_path.into();
+                let character_name = character_name.into();
+                let path = format!("{}/{}.chara.ron", dir_path, character_name);
+                log::info!("load character: {:?}", path);
+                let handle = loader.load(path, RonFormat, progress, &storage);
+                store.add_character(&character_name, handle);
+            },
+        );
+    }
+}
+
+impl HitLevelLoad for &mut World {
+    fn load_hit_level<F, N>(&mut self, dir_path: F, table_name: N, progress: &mut ProgressCounter)
+    where
+        F: Into<String>,
+        N: Into<String>,
+    {
+        self.exec(
+            |(mut store, loader, storage): (
+                Write<HitLevelStore>,
+                ReadExpect<Loader>,
+                Read<AssetStorage<HitLevelTable>>,
+            )| {
+                let path = format!("{}/{}.hit.ron", dir_path.into(), table_name.into());
+                log::info!("load hit level: {:?}", path);
+                let handle = loader.load(path, RonFormat, progress, &storage);
+                store.set_table(handle);
+            },
+        );
+    }
+}
+
 pub trait CommandLoad {
     fn load_command<F, C>(
         &mut self,
@@ -41,3 +93,27 @@ pub trait CommandLoad {
         F: Into<String>,
         C: Into<String>;
 }
+
+// ヒットレベルの設定表はゲーム全体で1つだけ読み込む
+pub trait HitLevelLoad {
+    fn load_hit_level<F, N>(
+        &mut self,
+        dir_path: F, // 設定表のあるディレクトリパス指定
+        table_name: N,
+        progress: &mut ProgressCounter,
+    ) where
+        F: Into<String>,
+        N: Into<String>;
+}
+
+// コマンドリストは定義に書かれた名前で CommandLoad を使って読み込む
+pub trait CharacterLoad {
+    fn load_character<F, C>(
+        &mut self,
+        dir_path: F, // キャラクター定義ファイルのあるディレクトリパス指定
+        character_name: C,
+        progress: &mut ProgressCounter,
+    ) where
+        F: Into<String>,
+        C: Into<String>;
+}
@@ -0,0 +1,339 @@
+mod packet;
+
+use crate::{
+    components::PlayerTag,
+    input::{InputFlag, InputQueue, QueueSource},
+    resource::{clock::FrameClock, input_source::InputSources},
+    simulation::Simulation,
+    snapshot::{GameSnapshot, SnapshotBuffer},
+};
+use amethyst::ecs::{World, WorldExt};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use packet::{InputPacket, MAX_PACKET_INPUTS};
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind},
+    net::{ToSocketAddrs, UdpSocket},
+};
+
+const RECEIVE_BUFFER_SIZE: usize = 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NetplayConfig {
+    pub local: PlayerTag,    // 自分が操作するプレイヤー
+    pub remote: PlayerTag,   // 通信相手が操作するプレイヤー
+    pub input_delay: u64,    // 入力遅延フレーム数
+    pub max_prediction: u64, // 相手の入力を予測で先行できる最大フレーム数
+}
+
+impl Default for NetplayConfig {
+    fn default() -> Self {
+        NetplayConfig {
+            local: PlayerTag::P1,
+            remote: PlayerTag::P2,
+            input_delay: 2,
+            max_prediction: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetplayEvent {
+    // 予測が外れたので from から to の直前まで再シミュレーションした
+    Rollback { from: u64, to: u64 },
+    // 確定したフレームの状態が一致しない
+    Desync { frame: u64, local: u64, remote: u64 },
+}
+
+// UDPで入力を交換するロールバック対戦セッション
+// 相手の入力が届いていないフレームは最後の入力を繰り返すと予測して進め，
+// 予測が外れていたらスナップショットから巻き戻して再シミュレーションする
+pub struct NetplaySession<T>
+where
+    T: AnimationFile,
+{
+    config: NetplayConfig,
+    socket: UdpSocket,
+    local_queue: InputQueue,
+    remote_queue: InputQueue,
+    frame: u64,            // 次にシミュレーションするフレーム
+    remote_confirmed: u64, // 相手の入力が揃っている次のフレーム
+    remote_ack: u64,       // 相手が受信済みの自分の入力(次に必要なフレーム)
+    local_inputs: BTreeMap<u64, InputFlag>,
+    remote_inputs: BTreeMap<u64, InputFlag>,
+    used_remote: BTreeMap<u64, InputFlag>, // シミュレーションに使った相手の入力
+    snapshots: SnapshotBuffer<T>,
+    local_checksum: Option<(u64, u64)>,
+    remote_checksums: BTreeMap<u64, u64>,
+    events: Vec<NetplayEvent>,
+}
+
+impl<T> NetplaySession<T>
+where
+    T: AnimationFile,
+{
+    pub fn bind<L, R>(local_addr: L, remote_addr: R, config: NetplayConfig) -> io::Result<Self>
+    where
+        L: ToSocketAddrs,
+        R: ToSocketAddrs,
+    {
+        let socket = UdpSocket::bind(local_addr)?;
+        socket.connect(remote_addr)?;
+        socket.set_nonblocking(true)?;
+        log::info!(
+            "netplay bind: {:?} => {:?}",
+            socket.local_addr(),
+            socket.peer_addr()
+        );
+
+        Ok(NetplaySession {
+            config,
+            socket,
+            local_queue: InputQueue::default(),
+            remote_queue: InputQueue::default(),
+            frame: 0,
+            remote_confirmed: 0,
+            remote_ack: 0,
+            local_inputs: BTreeMap::new(),
+            remote_inputs: BTreeMap::new(),
+            used_remote: BTreeMap::new(),
+            snapshots: SnapshotBuffer::new(config.max_prediction as usize + 2),
+            local_checksum: None,
+            remote_checksums: BTreeMap::new(),
+            events: vec![],
+        })
+    }
+
+    // ワールドの入力元をセッション用に切り替える
+    pub fn start(&mut self, world: &mut World) {
+        self.frame = world.read_resource::<FrameClock>().frame();
+        self.remote_confirmed = self.frame;
+        self.remote_ack = self.frame;
+
+        let (local_source, local_queue) = QueueSource::new();
+        let (remote_source, remote_queue) = QueueSource::new();
+        {
+            let mut sources = world.write_resource::<InputSources>();
+            sources.set_source(self.config.local, local_source);
+            sources.set_source(self.config.remote, remote_source);
+        }
+        self.local_queue = local_queue;
+        self.remote_queue = remote_queue;
+
+        // 入力遅延分は入力なしで埋める
+        for f in self.frame..self.frame + self.config.input_delay {
+            self.local_inputs.insert(f, InputFlag::empty());
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn drain_events(&mut self) -> impl Iterator<Item = NetplayEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    // 自分の入力を登録して1フレーム進める
+    // 相手の入力を待つ必要がある場合は進めずに false を返す
+    pub fn advance<'a, 'b>(
+        &mut self,
+        world: &mut World,
+        simulation: &mut Simulation<'a, 'b>,
+        local_input: InputFlag,
+    ) -> io::Result<bool> {
+        self.receive()?;
+        self.rollback(world, simulation);
+
+        if self.frame >= self.remote_confirmed + self.config.max_prediction {
+            // 予測で進めすぎているので相手を待つ
+            self.send()?;
+            return Ok(false);
+        }
+
+        self.local_inputs
+            .insert(self.frame + self.config.input_delay, local_input);
+        self.send()?;
+
+        let frame = self.frame;
+        self.simulate(world, simulation, frame);
+        self.frame += 1;
+
+        self.update_checksum();
+        self.verify_checksum();
+        self.discard_old_inputs();
+
+        Ok(true)
+    }
+
+    fn simulate<'a, 'b>(
+        &mut self,
+        world: &mut World,
+        simulation: &mut Simulation<'a, 'b>,
+        frame: u64,
+    ) {
+        // フレーム開始時の状態を保存
+        self.snapshots.save(GameSnapshot::capture(world));
+
+        let local = self
+            .local_inputs
+            .get(&frame)
+            .cloned()
+            .unwrap_or(InputFlag::empty());
+        let remote = match self.remote_inputs.get(&frame) {
+            Some(&input) => input,
+            None => self.predict(frame),
+        };
+        self.used_remote.insert(frame, remote);
+
+        self.local_queue.clear();
+        self.remote_queue.clear();
+        self.local_queue.push(local);
+        self.remote_queue.push(remote);
+
+        simulation.step(world);
+    }
+
+    // 届いていない相手の入力は最後に届いた入力を繰り返すと予測
+    fn predict(&self, frame: u64) -> InputFlag {
+        self.remote_inputs
+            .range(..frame)
+            .next_back()
+            .map(|(_, &input)| input)
+            .unwrap_or(InputFlag::empty())
+    }
+
+    // 予測が外れたフレームがあれば巻き戻して再シミュレーション
+    fn rollback<'a, 'b>(&mut self, world: &mut World, simulation: &mut Simulation<'a, 'b>) {
+        let mispredicted = self
+            .used_remote
+            .iter()
+            .find(|(f, used)| {
+                self.remote_inputs
+                    .get(*f)
+                    .map(|input| input != *used)
+                    .unwrap_or(false)
+            })
+            .map(|(&f, _)| f);
+
+        if let Some(from) = mispredicted {
+            match self.snapshots.get(from) {
+                Some(snapshot) => snapshot.restore(world),
+                None => {
+                    log::error!("rollback snapshot not found: {} F", from);
+                    return;
+                }
+            }
+
+            let to = self.frame;
+            log::debug!("rollback: {} F => {} F", from, to);
+            for f in from..to {
+                self.simulate(world, simulation, f);
+            }
+            self.events.push(NetplayEvent::Rollback { from, to });
+        }
+
+        // 確定したフレームは予測の記録が不要
+        let confirmed = self.remote_confirmed;
+        self.used_remote = self.used_remote.split_off(&confirmed);
+    }
+
+    // 相手の入力が揃ったフレームのチェックサムを記録
+    fn update_checksum(&mut self) {
+        // スナップショットは開始時の状態なので，直前のフレームまでの入力で確定する
+        let frame = self.remote_confirmed.min(self.frame - 1);
+        if let Some(checksum) = self.snapshots.checksum(frame) {
+            self.local_checksum = Some((frame, checksum));
+        }
+    }
+
+    fn verify_checksum(&mut self) {
+        if let Some((frame, local)) = self.local_checksum {
+            if let Some(remote) = self.remote_checksums.remove(&frame) {
+                if local != remote {
+                    log::error!("desync: {} F, {:x} != {:x}", frame, local, remote);
+                    self.events.push(NetplayEvent::Desync {
+                        frame,
+                        local,
+                        remote,
+                    });
+                }
+            }
+            self.remote_checksums = self.remote_checksums.split_off(&frame);
+        }
+    }
+
+    fn discard_old_inputs(&mut self) {
+        let keep_from = self
+            .frame
+            .saturating_sub(self.config.max_prediction + MAX_PACKET_INPUTS as u64);
+        self.local_inputs = self.local_inputs.split_off(&keep_from.min(self.remote_ack));
+        self.remote_inputs = self.remote_inputs.split_off(&keep_from);
+    }
+
+    fn send(&self) -> io::Result<()> {
+        let start = self
+            .local_inputs
+            .keys()
+            .next()
+            .map(|&first| first.max(self.remote_ack))
+            .unwrap_or(self.remote_ack);
+        let inputs = self
+            .local_inputs
+            .range(start..)
+            .take(MAX_PACKET_INPUTS)
+            .map(|(_, &input)| input)
+            .collect::<Vec<_>>();
+
+        let packet = InputPacket {
+            ack: self.remote_confirmed,
+            start,
+            inputs,
+            checksum: self.local_checksum,
+        };
+
+        match self.socket.send(&packet.encode()) {
+            Ok(_) => Ok(()),
+            // 相手がまだ起動していない場合などは次のフレームで再送される
+            Err(err) if err.kind() == ErrorKind::ConnectionRefused => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        let mut buffer = [0u8; RECEIVE_BUFFER_SIZE];
+        loop {
+            let len = match self.socket.recv(&mut buffer) {
+                Ok(len) => len,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::ConnectionRefused => break,
+                Err(err) => return Err(err),
+            };
+
+            let packet = match InputPacket::decode(&buffer[..len]) {
+                Some(packet) => packet,
+                None => {
+                    log::warn!("invalid packet: {} bytes", len);
+                    continue;
+                }
+            };
+
+            self.remote_ack = self.remote_ack.max(packet.ack);
+            for (i, input) in packet.inputs.into_iter().enumerate() {
+                let frame = packet.start + i as u64;
+                if frame >= self.remote_confirmed {
+                    self.remote_inputs.insert(frame, input);
+                }
+            }
+            if let Some((frame, checksum)) = packet.checksum {
+                self.remote_checksums.insert(frame, checksum);
+            }
+        }
+
+        while self.remote_inputs.contains_key(&self.remote_confirmed) {
+            self.remote_confirmed += 1;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::input::InputFlag;
+use std::convert::TryInto;
+
+// 1パケットに詰める最大入力数
+pub(crate) const MAX_PACKET_INPUTS: usize = 64;
+
+// 通信相手に送る入力パケット
+// [ack: u64][start: u64][count: u16][input: u16 * count][checksum有無: u8][frame: u64][checksum: u64]
+#[derive(Debug, PartialEq)]
+pub(crate) struct InputPacket {
+    pub(crate) ack: u64, // 相手の入力をどのフレームまで受信済みか(次に必要なフレーム)
+    pub(crate) start: u64, // inputs の先頭フレーム
+    pub(crate) inputs: Vec<InputFlag>,
+    pub(crate) checksum: Option<(u64, u64)>, // 確定したフレームとそのチェックサム
+}
+
+impl InputPacket {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let count = self.inputs.len().min(MAX_PACKET_INPUTS);
+        let mut bytes = Vec::with_capacity(8 + 8 + 2 + count * 2 + 1 + 16);
+        bytes.extend_from_slice(&self.ack.to_le_bytes());
+        bytes.extend_from_slice(&self.start.to_le_bytes());
+        bytes.extend_from_slice(&(count as u16).to_le_bytes());
+        for input in self.inputs.iter().take(count) {
+            bytes.extend_from_slice(&(input.bits() as u16).to_le_bytes());
+        }
+        match self.checksum {
+            Some((frame, checksum)) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&frame.to_le_bytes());
+                bytes.extend_from_slice(&checksum.to_le_bytes());
+            }
+            None => bytes.push(0),
+        }
+        bytes
+    }
+
+    pub(crate) fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader { bytes, position: 0 };
+        let ack = reader.u64()?;
+        let start = reader.u64()?;
+        let count = reader.u16()? as usize;
+        let inputs = (0..count)
+            .map(|_| {
+                reader
+                    .u16()
+                    .map(|bits| InputFlag::from_bits_truncate(bits as u64))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let checksum = match reader.u8()? {
+            0 => None,
+            _ => Some((reader.u64()?, reader.u64()?)),
+        };
+        Some(InputPacket {
+            ack,
+            start,
+            inputs,
+            checksum,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.bytes.get(self.position..self.position + len)?;
+        self.position += len;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
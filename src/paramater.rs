@@ -1,11 +1,15 @@
 mod animation_paramater;
 mod change_paramater;
+mod character_paramater;
 mod collision_paramater;
 mod collision_type;
+mod spawn_paramater;
 mod translate_animation;
 
-pub use animation_paramater::AnimationParam;
+pub use animation_paramater::{AnimationParam, Armor};
 pub use change_paramater::ChangeParamater;
+pub use character_paramater::{GaugeParamater, JumpParamater, MoveParamater};
 pub use collision_paramater::CollisionParamater;
-pub use collision_type::{CollisionType, HitLevel};
+pub use collision_type::{BlowInfo, CollisionType, HitLevel, Knockdown};
+pub use spawn_paramater::SpawnParamater;
 pub use translate_animation::FightTranslation;
@@ -1,6 +1,6 @@
 use crate::{
-    flag::Cancel,
-    paramater::{ChangeParamater, CollisionType, FightTranslation},
+    flag::{Cancel, Invulnerable},
+    paramater::{ChangeParamater, CollisionType, FightTranslation, SpawnParamater},
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +10,20 @@ pub struct AnimationParam {
     #[serde(default, skip_serializing_if = "Cancel::is_empty")]
     pub cancel: Cancel,
     pub change: Option<ChangeParamater<FightTranslation>>,
+    #[serde(default, skip_serializing_if = "Invulnerable::is_empty")]
+    pub invulnerable: Invulnerable, // やられ判定に設定する無敵
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub armor: Option<Armor>, // やられ判定に設定するアーマー
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn: Option<SpawnParamater>, // 弾の生成
+}
+
+// アーマー(ダメージは受けるがのけぞらない)
+// 投げは受け止められない
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Armor {
+    Super,              // 何度攻撃を受けてものけぞらない
+    Hit { count: u32 }, // 1回の技で指定回数まで受け止める
 }
 
 impl AnimationParam {}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// 地上での移動速度(1フレームの移動量)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct MoveParamater {
+    pub walk_speed: f32,      // 前歩き
+    pub back_walk_speed: f32, // 後ろ歩き
+    pub dash_speed: f32,      // ダッシュ
+    pub back_dash_speed: f32, // 後ろダッシュ
+}
+
+// ジャンプの移動量
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct JumpParamater {
+    pub vertical_speed: f32,   // 上昇の初速
+    pub horizontal_speed: f32, // 前後ジャンプの横方向の速度
+    pub gravity: f32,          // 1フレームに減る上昇速度
+}
+
+// ゲージの設定
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct GaugeParamater {
+    pub max: f32,
+    #[serde(default)]
+    pub initial: f32, // ラウンド開始時の量
+}
@@ -1,6 +1,7 @@
 use crate::{
-    components::{Damaged, SkillCount},
-    paramater::{AnimationParam, CollisionType},
+    components::{ArmorCount, Damaged, Down, Projectile, SkillCount},
+    flag::Invulnerable,
+    paramater::{AnimationParam, Armor, CollisionType},
     traits::{ExtrudeFilter, ParamaterFromData},
     types::DamageCollisionId,
 };
@@ -16,8 +17,54 @@ where
     T: AnimationFile,
 {
     pub collision_type: CollisionType,
+    pub owner: Entity, // 判定の持ち主(弾なら生成元)
     pub collision_id: Option<DamageCollisionId<T>>,
     pub damaged_collision_ids: Option<Damaged<T>>,
+    pub invulnerable: Invulnerable, // やられ判定の無敵
+    pub armor: Option<Armor>,       // やられ判定のアーマー
+    pub armor_count: u32,           // 現在の技でアーマーが受け止めた回数
+    pub position: (f32, f32),       // 判定の中心座標
+    pub size: (f32, f32),           // 判定の大きさ
+}
+
+impl<T> CollisionParamater<T>
+where
+    T: AnimationFile,
+{
+    // 攻撃の種類に対して無敵か
+    pub fn is_invulnerable(&self, attack_type: &CollisionType) -> bool {
+        match attack_type {
+            CollisionType::Blow { .. } => self.invulnerable.contains(Invulnerable::STRIKE),
+            CollisionType::Projectile { .. } => {
+                self.invulnerable.contains(Invulnerable::PROJECTILE)
+            }
+            CollisionType::Throw => self.invulnerable.contains(Invulnerable::THROW),
+            _ => false,
+        }
+    }
+
+    // 相手の判定と重なった範囲の中心
+    pub fn contact_point(&self, other: &Self) -> (f32, f32) {
+        let overlap = |p1: f32, s1: f32, p2: f32, s2: f32| {
+            let min = (p1 - s1 / 2.).max(p2 - s2 / 2.);
+            let max = (p1 + s1 / 2.).min(p2 + s2 / 2.);
+            (min + max) / 2.
+        };
+        (
+            overlap(self.position.0, self.size.0, other.position.0, other.size.0),
+            overlap(self.position.1, self.size.1, other.position.1, other.size.1),
+        )
+    }
+
+    // アーマーで攻撃を受け止められるか
+    // absorbed は同フレームにすでに受け止めた攻撃(判定ID)の数
+    pub fn is_armored(&self, attack_type: &CollisionType, absorbed: u32) -> bool {
+        match (attack_type, self.armor) {
+            (CollisionType::Throw, _) | (_, None) => false,
+            (_, Some(Armor::Super)) => true,
+            (_, Some(Armor::Hit { count })) => self.armor_count + absorbed < count,
+        }
+    }
 }
 
 impl<T> CollisionObject for CollisionParamater<T>
@@ -29,18 +76,47 @@ where
             // 押し出し判定は存在するならOK
             (CollisionType::Extrusion, CollisionType::Extrusion) => true,
 
-            // ダメージvs攻撃なら，ダメージ側が攻撃側の判定とぶつかったことがないかチェック
+            // ダメージvs攻撃なら，自分の攻撃でなく，ダメージ側が無敵でなく攻撃側の判定とぶつかったことがないかチェック
             (CollisionType::Damaged, CollisionType::Blow { .. })
             | (CollisionType::Damaged, CollisionType::Projectile { .. })
             | (CollisionType::Damaged, CollisionType::Throw) => {
-                yet_nothit_collision(p2.collision_id.as_ref(), p1.damaged_collision_ids.as_ref())
+                p1.owner != p2.owner
+                    && p1.is_invulnerable(&p2.collision_type) == false
+                    && yet_nothit_collision(
+                        p2.collision_id.as_ref(),
+                        p1.damaged_collision_ids.as_ref(),
+                    )
             }
-            // 攻撃vsダメージなら，ダメージ側が攻撃側の判定とぶつかったことがないかチェック
+            // 攻撃vsダメージなら，自分の攻撃でなく，ダメージ側が無敵でなく攻撃側の判定とぶつかったことがないかチェック
             (CollisionType::Blow { .. }, CollisionType::Damaged)
             | (CollisionType::Projectile { .. }, CollisionType::Damaged)
             | (CollisionType::Throw, CollisionType::Damaged) => {
-                yet_nothit_collision(p1.collision_id.as_ref(), p2.damaged_collision_ids.as_ref())
+                p1.owner != p2.owner
+                    && p2.is_invulnerable(&p1.collision_type) == false
+                    && yet_nothit_collision(
+                        p1.collision_id.as_ref(),
+                        p2.damaged_collision_ids.as_ref(),
+                    )
+            }
+            // 攻撃同士，弾同士なら，お互いの判定とぶつかったことがないかチェック
+            // 攻撃同士の相殺が無効な場合はヒット情報の更新時に無視する
+            (CollisionType::Blow { .. }, CollisionType::Blow { .. })
+            | (CollisionType::Projectile { .. }, CollisionType::Projectile { .. }) => {
+                p1.owner != p2.owner
+                    && yet_nothit_collision(
+                        p1.collision_id.as_ref(),
+                        p2.damaged_collision_ids.as_ref(),
+                    )
+                    && yet_nothit_collision(
+                        p2.collision_id.as_ref(),
+                        p1.damaged_collision_ids.as_ref(),
+                    )
             }
+            // 弾と反射，吸収判定なら相手の弾かチェック
+            (CollisionType::Projectile { .. }, CollisionType::Reflect)
+            | (CollisionType::Projectile { .. }, CollisionType::Absorb)
+            | (CollisionType::Reflect, CollisionType::Projectile { .. })
+            | (CollisionType::Absorb, CollisionType::Projectile { .. }) => p1.owner != p2.owner,
             _ => false,
         }
     }
@@ -69,13 +145,31 @@ where
         ReadStorage<'s, PlayAnimationKey<T>>,
         ReadStorage<'s, SkillCount<T>>,
         ReadStorage<'s, Damaged<T>>,
+        // 無敵判定に必要
+        ReadStorage<'s, Down>,
+        // アーマーの回数判定に必要
+        ReadStorage<'s, ArmorCount<T>>,
+        // 弾の生成元の判定に必要
+        ReadStorage<'s, Projectile>,
     );
     fn make_collision_data(
         entity: Entity,
         param: Option<&AnimationParam>,
-        (keys, skill_counts, damaged): &Self::SystemData,
+        (keys, skill_counts, damaged, downs, armor_counts, projectiles): &Self::SystemData,
     ) -> Option<Self> {
-        let collision_type = param?.collision_type?;
+        let param = param?;
+        let collision_type = param.collision_type?;
+
+        // ダウン，起き上がり中はやられ判定を出さない
+        if let CollisionType::Damaged = collision_type {
+            if downs
+                .get(entity)
+                .map(|d| d.is_invulnerable())
+                .unwrap_or(false)
+            {
+                return None;
+            }
+        }
 
         let collision_id = match &collision_type {
             &CollisionType::Blow {
@@ -102,12 +196,49 @@ where
             _ => None,
         };
 
+        let (invulnerable, armor, armor_count) = match &collision_type {
+            CollisionType::Damaged => {
+                // ダウン，起き上がり中はやられ判定自体を出さないので，ここでは技の無敵だけを見る
+                let armor_count = keys
+                    .get(entity)
+                    .and_then(|key| key.play_key())
+                    .and_then(|(&file, &pack, &anim)| {
+                        let count = skill_counts
+                            .get(entity)
+                            .map(|count| count.skill_count(&(file, pack, anim)))
+                            .unwrap_or(0);
+                        armor_counts
+                            .get(entity)
+                            .map(|armor| armor.count(&(file, pack, anim, count)))
+                    })
+                    .unwrap_or(0);
+                (param.invulnerable, param.armor, armor_count)
+            }
+            _ => (Invulnerable::empty(), None, 0),
+        };
+
+        let owner = projectiles
+            .get(entity)
+            .map(|projectile| projectile.owner())
+            .unwrap_or(entity);
+
         Some(CollisionParamater {
             collision_type,
+            owner,
             collision_id,
             damaged_collision_ids: damaged.get(entity).cloned(),
+            invulnerable,
+            armor,
+            armor_count,
+            position: (0., 0.),
+            size: (0., 0.),
         })
     }
+
+    fn set_bounds(&mut self, position: (f32, f32), size: (f32, f32)) {
+        self.position = position;
+        self.size = size;
+    }
 }
 
 impl<'s, T> ExtrudeFilter<'s> for CollisionParamater<T>
@@ -143,6 +274,8 @@ where
             CollisionType::Projectile { .. } => (0., 1., 0., 1.),
             CollisionType::Throw => (0., 0., 1., 1.),
             CollisionType::Damaged => (1., 1., 0., 1.),
+            CollisionType::Reflect => (0., 1., 1., 1.),
+            CollisionType::Absorb => (1., 1., 1., 1.),
         }
     }
 }
@@ -154,8 +287,14 @@ where
     fn clone(&self) -> Self {
         CollisionParamater {
             collision_type: self.collision_type,
+            owner: self.owner,
             collision_id: self.collision_id,
             damaged_collision_ids: self.damaged_collision_ids.clone(),
+            invulnerable: self.invulnerable,
+            armor: self.armor,
+            armor_count: self.armor_count,
+            position: self.position,
+            size: self.size,
         }
     }
 }
@@ -9,6 +9,8 @@ pub enum CollisionType {
         damage: f32,      // ダメージ
         air: BlowInfo,    // 空中ヒット時
         ground: BlowInfo, // 地上ヒット時
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        guard: Option<BlowInfo>, // ガード時
         hit_level: HitLevel,
         #[serde(default, skip_serializing_if = "count_zero")]
         collision_count: u32,
@@ -18,12 +20,16 @@ pub enum CollisionType {
         damage: f32,      // ダメージ
         air: BlowInfo,    // 空中ヒット時
         ground: BlowInfo, // 地上ヒット時
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        guard: Option<BlowInfo>, // ガード時
         hit_level: HitLevel,
         #[serde(default, skip_serializing_if = "count_zero")]
         collision_count: u32,
     },
     Throw,
     Damaged, // 被ダメージ
+    Reflect, // 弾を跳ね返す
+    Absorb,  // 弾を消す
 }
 
 // 攻撃ヒット時の硬直情報
@@ -32,6 +38,49 @@ pub struct BlowInfo {
     pub(crate) x: f32,       // 移動速度初期値
     pub(crate) y: f32,       // 移動速度初期値
     pub(crate) frame: usize, // ヒットフレーム
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) knockdown: Option<Knockdown>, // ダウン属性(ノックバック後にダウンする)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) ground_bounce: bool, // 地面バウンド
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) wall_bounce: bool, // 壁バウンド
+}
+
+impl BlowInfo {
+    // 移動，ダウンのない硬直のみの情報
+    pub(crate) fn from_frame(frame: usize) -> Self {
+        BlowInfo {
+            x: 0.,
+            y: 0.,
+            frame,
+            knockdown: None,
+            ground_bounce: false,
+            wall_bounce: false,
+        }
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn knockdown(&self) -> Option<Knockdown> {
+        self.knockdown
+    }
+
+    pub fn ground_bounce(&self) -> bool {
+        self.ground_bounce
+    }
+
+    pub fn wall_bounce(&self) -> bool {
+        self.wall_bounce
+    }
+}
+
+// ダウンの種類
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Knockdown {
+    Soft, // 受け身可能
+    Hard, // 受け身不能
 }
 
 // ヒットレベル情報
@@ -41,7 +90,12 @@ pub enum HitLevel {
     Level2,
     Level3,
     Level4,
-    Custom { level: u8, frame: usize },
+    Custom {
+        level: u8,
+        frame: usize, // 攻撃を受けた側のヒットストップ
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attacker_frame: Option<usize>, // 攻撃した側のヒットストップ(省略時は frame と同じ)
+    },
 }
 
 impl HitLevel {
@@ -56,7 +110,8 @@ impl HitLevel {
         }
     }
 
-    pub fn hitstop(&self) -> usize {
+    // 攻撃を受けた側のヒットストップ
+    pub fn defender_hitstop(&self) -> usize {
         use HitLevel::*;
         match self {
             Level1 => 12,
@@ -66,6 +121,19 @@ impl HitLevel {
             &Custom { frame, .. } => frame,
         }
     }
+
+    // 攻撃した側のヒットストップ
+    // データで指定しなければ受けた側と同じ
+    pub fn attacker_hitstop(&self) -> usize {
+        match self {
+            &HitLevel::Custom {
+                frame,
+                attacker_frame,
+                ..
+            } => attacker_frame.unwrap_or(frame),
+            _ => self.defender_hitstop(),
+        }
+    }
 }
 
 impl Ord for HitLevel {
@@ -89,3 +157,7 @@ impl PartialEq for HitLevel {
 fn count_zero(count: &u32) -> bool {
     *count == 0
 }
+
+fn is_false(flag: &bool) -> bool {
+    *flag == false
+}
@@ -0,0 +1,49 @@
+use crate::id::{
+    file::FileId,
+    pack::{AnimationKey, PackKey},
+};
+use serde::{Deserialize, Serialize};
+
+// 弾の生成情報
+// このパラメータを持つパーツが表示されたときに1度だけ生成する
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct SpawnParamater {
+    #[serde(default)]
+    pub(crate) id: u32, // 同じアニメーション内で複数生成する場合の識別子
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) file: Option<FileId>, // 省略時は生成元と同じファイル
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pack: Option<PackKey>, // 省略時は生成元と同じパック
+    pub(crate) animation: AnimationKey,
+    #[serde(default)]
+    pub(crate) offset: (f32, f32), // 生成位置(向き基準)
+    #[serde(default)]
+    pub(crate) velocity: (f32, f32), // 1フレームの移動量(向き基準)
+    pub(crate) lifetime: usize, // 消えるまでのフレーム数
+    #[serde(default = "default_count")]
+    pub(crate) hit_count: u32, // 消えるまでにヒットできる回数
+    #[serde(default = "default_count")]
+    pub(crate) durability: u32, // 弾同士の相殺で使う耐久値
+}
+
+impl SpawnParamater {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn lifetime(&self) -> usize {
+        self.lifetime
+    }
+
+    pub fn hit_count(&self) -> u32 {
+        self.hit_count
+    }
+
+    pub fn durability(&self) -> u32 {
+        self.durability
+    }
+}
+
+fn default_count() -> u32 {
+    1
+}
@@ -1,6 +1,6 @@
 use crate::{
-    components::{ActiveCommand, Knockback, SkillSet},
-    flag::Condition,
+    components::{ActiveCommand, ClashCancel, Down, Knockback, SkillSet},
+    flag::{Cancel, Condition},
     id::{file, pack},
     paramater::AnimationParam,
 };
@@ -11,7 +11,6 @@ use amethyst::{
 use amethyst_sprite_studio::traits::{
     animation_file::AnimationFile, translate_animation::TranslateAnimation,
 };
-use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy)]
 pub struct FightTranslation;
@@ -25,6 +24,8 @@ type FightOptionalData<'s> = (
     ReadStorage<'s, SkillSet>,
     ReadStorage<'s, Knockback>,
     ReadStorage<'s, Transform>,
+    ReadStorage<'s, Down>,
+    ReadStorage<'s, ClashCancel>,
 );
 impl AnimationFile for FightTranslation {
     type FileId = FightFileId;
@@ -33,11 +34,11 @@ impl AnimationFile for FightTranslation {
     type UserData = FightUserData;
 
     fn to_file_name(file_id: &Self::FileId) -> &'static str {
-        FILE_LIST[file_id].0
+        file_id.file_name()
     }
 
     fn sprite_sheet_num(file_id: &Self::FileId) -> usize {
-        FILE_LIST[file_id].1
+        file_id.sprite_sheet_num()
     }
 }
 
@@ -49,7 +50,7 @@ impl<'s> TranslateAnimation<'s> for FightTranslation {
         rest_time: Option<usize>,
         pack_anim_key: (&Self::PackKey, &Self::AnimationKey),
         user: Option<&Self::UserData>,
-        (active_commands, skill_sets, knockback, transform): &Self::OptionalData,
+        (active_commands, skill_sets, knockback, transform, down, clash_cancels): &Self::OptionalData,
     ) -> Option<(Self::PackKey, Self::AnimationKey, usize)> {
         if let Some((change_pack, change_anim)) = user
             .and_then(|user| user.change.as_ref())
@@ -71,6 +72,24 @@ impl<'s> TranslateAnimation<'s> for FightTranslation {
                     {
                         // 座標が基準位置以上ならこの条件で遷移
                         true
+                    } else if condition.contains(Condition::KNOCKDOWN)
+                        && down.get(entity).map(|d| d.is_lying()).unwrap_or(false)
+                    {
+                        // ダウン中ならこの条件で遷移
+                        true
+                    } else if condition.contains(Condition::TECH_ROLL)
+                        && down
+                            .get(entity)
+                            .map(|d| d.tech_roll().is_some())
+                            .unwrap_or(false)
+                    {
+                        // 受け身中ならこの条件で遷移
+                        true
+                    } else if condition.contains(Condition::WAKE_UP)
+                        && down.get(entity).map(|d| d.is_wake_up()).unwrap_or(false)
+                    {
+                        // 起き上がり中ならこの条件で遷移
+                        true
                     } else {
                         // 条件を満たさないので遷移しない
                         false
@@ -84,8 +103,13 @@ impl<'s> TranslateAnimation<'s> for FightTranslation {
         } else {
             let active = active_commands.get(entity)?;
             let skill_set = skill_sets.get(entity)?;
+            // 相殺後の猶予中は追加でキャンセルできる
+            let clash_cancel = clash_cancels
+                .get(entity)
+                .map(|clash| clash.cancel())
+                .unwrap_or(Cancel::empty());
             let next = if rest_time.is_some() {
-                on_during_animation(pack_anim_key, user, active, skill_set)
+                on_during_animation(pack_anim_key, user, clash_cancel, active, skill_set)
             } else {
                 on_finish_animation(pack_anim_key, user, active, skill_set)
             };
@@ -100,14 +124,15 @@ impl<'s> TranslateAnimation<'s> for FightTranslation {
 fn on_during_animation(
     (&current_pack, current_anim): (&FightPackKey, &FightAnimationKey),
     user: Option<&FightUserData>,
+    clash_cancel: Cancel,
     active: &ActiveCommand,
     skill_set: &SkillSet,
 ) -> Option<(FightPackKey, FightAnimationKey, usize)> {
     // とりあえずenum値的に最大値を優先する
-    let user = user?;
+    let cancel = user.map(|user| user.cancel).unwrap_or(Cancel::empty()) | clash_cancel;
     let command = active
         .active_commands()
-        .filter(|command| user.cancel.is_cancelable(command))
+        .filter(|command| cancel.is_cancelable(command))
         .max()?;
     log::debug!("canceled: {:?}", command);
 
@@ -133,12 +158,3 @@ fn on_finish_animation(
         .unwrap_or(skill_set.neutral_skill());
     Some((current_pack, *skill, 0))
 }
-
-lazy_static::lazy_static! {
-    static ref FILE_LIST: BTreeMap<file::FileId, (&'static str, usize)> = {
-        let mut list = BTreeMap::new();
-        list.insert(file::FileId::Sample, ("sample", 1));
-        list.insert(file::FileId::Sandbox, ("sandbox", 1));
-        list
-    };
-}
@@ -0,0 +1,144 @@
+use crate::{
+    components::{
+        ActiveCommand, CharacterStatus, Direction, Gauge, Health, PlayerTag, SkillCount, SkillSet,
+    },
+    paramater::FightTranslation,
+    resource::{
+        character::{CharacterDefinition, CharacterStore},
+        command::CommandStore,
+    },
+};
+use amethyst::{
+    assets::{AssetStorage, PrefabData},
+    core::Transform,
+    ecs::{Entity, Read, ReadExpect, World, WorldExt, WriteStorage},
+    error::Error,
+};
+use amethyst_sprite_studio::components::{AnimationTime, PlayAnimationKey};
+use serde::{Deserialize, Serialize};
+
+// キャラクター定義から戦闘可能なエンティティを生成するプレハブ
+// 定義はあらかじめ CharacterLoad で読み込んでおく
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CharacterPrefab {
+    pub character: String, // 読み込んだキャラクター定義の名前
+    pub tag: PlayerTag,
+    pub position: (f32, f32),
+    pub direction: Direction,
+}
+
+impl CharacterPrefab {
+    pub fn new(
+        character: &str,
+        tag: PlayerTag,
+        position: (f32, f32),
+        direction: Direction,
+    ) -> Self {
+        CharacterPrefab {
+            character: character.into(),
+            tag,
+            position,
+            direction,
+        }
+    }
+
+    // プレハブを使わずに直接生成する
+    pub fn spawn(&self, world: &mut World) -> Result<Entity, Error> {
+        let entity = world.create_entity().build();
+        world.exec(|mut data: <Self as PrefabData<'_>>::SystemData| {
+            self.add_to_entity(entity, &mut data, &[entity], &[])
+        })?;
+        Ok(entity)
+    }
+}
+
+impl<'a> PrefabData<'a> for CharacterPrefab {
+    type SystemData = (
+        ReadExpect<'a, CharacterStore>,
+        Read<'a, AssetStorage<CharacterDefinition>>,
+        ReadExpect<'a, CommandStore>,
+        WriteStorage<'a, PlayerTag>,
+        WriteStorage<'a, Direction>,
+        WriteStorage<'a, Transform>,
+        WriteStorage<'a, PlayAnimationKey<FightTranslation>>,
+        WriteStorage<'a, AnimationTime>,
+        WriteStorage<'a, SkillSet>,
+        WriteStorage<'a, SkillCount<FightTranslation>>,
+        WriteStorage<'a, ActiveCommand>,
+        WriteStorage<'a, Health>,
+        WriteStorage<'a, Gauge>,
+        WriteStorage<'a, CharacterStatus>,
+    );
+    type Result = ();
+
+    fn add_to_entity(
+        &self,
+        entity: Entity,
+        (
+            store,
+            storage,
+            commands,
+            tags,
+            directions,
+            transforms,
+            keys,
+            times,
+            skill_sets,
+            skill_counts,
+            active_commands,
+            healths,
+            gauges,
+            statuses,
+        ): &mut Self::SystemData,
+        _: &[Entity],
+        _: &[Entity],
+    ) -> Result<(), Error> {
+        let definition = store
+            .character(&self.character)
+            .and_then(|handle| storage.get(handle))
+            .ok_or_else(|| {
+                Error::from_string(format!("character not loaded: {}", self.character))
+            })?;
+        log::info!("spawn character: {} {:?}", self.character, self.tag);
+
+        // コマンドリストが無くても動くが技が出せない
+        if commands
+            .command_names()
+            .any(|name| name == definition.command())
+            == false
+        {
+            log::warn!("command list not loaded: {}", definition.command());
+        }
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(self.position.0, self.position.1, 0.);
+
+        let mut key = PlayAnimationKey::<FightTranslation>::new(definition.file());
+        key.set_pack(definition.pack());
+        key.set_animation(*definition.skill_set().neutral_skill());
+
+        let mut gauge = Gauge::new(definition.gauge().max);
+        gauge.add(definition.gauge().initial);
+
+        tags.insert(entity, self.tag)?;
+        directions.insert(entity, self.direction)?;
+        transforms.insert(entity, transform)?;
+        keys.insert(entity, key)?;
+        times.insert(entity, AnimationTime::new())?;
+        skill_sets.insert(entity, definition.skill_set().clone())?;
+        skill_counts.insert(entity, SkillCount::new())?;
+        active_commands.insert(entity, ActiveCommand::new())?;
+        healths.insert(entity, Health::new(definition.health()))?;
+        gauges.insert(entity, gauge)?;
+        statuses.insert(
+            entity,
+            CharacterStatus::new(
+                self.character.clone(),
+                *definition.movement(),
+                *definition.jump(),
+            ),
+        )?;
+
+        Ok(())
+    }
+}
@@ -1 +1,18 @@
+pub mod ai;
+pub mod character;
+pub mod clash;
+pub mod clock;
 pub mod command;
+pub mod diagnostic;
+pub mod hit_event;
+pub mod hit_level;
+pub mod input_history;
+pub mod input_source;
+pub mod knockdown;
+pub mod match_state;
+pub mod random;
+pub mod replay;
+pub mod stage;
+pub mod trade;
+pub mod training;
+pub mod training_hud;
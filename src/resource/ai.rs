@@ -0,0 +1,159 @@
+use crate::{
+    components::PlayerTag,
+    input::{InputFlag, InputQueue},
+};
+use std::collections::{BTreeMap, VecDeque};
+
+// CPUの強さ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiDifficulty {
+    Easy,
+    Normal,
+    Hard,
+    Custom {
+        reaction_delay: usize, // 相手の行動に反応するまでのフレーム数
+        error_rate: f32,       // 入力を間違える確率
+    },
+}
+
+impl AiDifficulty {
+    pub fn reaction_delay(&self) -> usize {
+        match self {
+            AiDifficulty::Easy => 30,
+            AiDifficulty::Normal => 18,
+            AiDifficulty::Hard => 8,
+            &AiDifficulty::Custom { reaction_delay, .. } => reaction_delay,
+        }
+    }
+
+    pub fn error_rate(&self) -> f32 {
+        match self {
+            AiDifficulty::Easy => 0.3,
+            AiDifficulty::Normal => 0.1,
+            AiDifficulty::Hard => 0.02,
+            &AiDifficulty::Custom { error_rate, .. } => error_rate,
+        }
+    }
+}
+
+// CPUの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiConfig {
+    pub difficulty: AiDifficulty,
+    pub seed: u64,           // 同じシードなら同じ入力になる
+    pub attack_range: f32,   // 攻撃を出す距離
+    pub approach_range: f32, // これより離れていたら近づく
+    pub guard_rate: f32,     // 相手の攻撃に反応したときにガードする確率
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        AiConfig {
+            difficulty: AiDifficulty::Normal,
+            seed: 0,
+            attack_range: 80.,
+            approach_range: 200.,
+            guard_rate: 0.8,
+        }
+    }
+}
+
+impl AiConfig {
+    pub fn new(difficulty: AiDifficulty, seed: u64) -> Self {
+        AiConfig {
+            difficulty,
+            seed,
+            ..Default::default()
+        }
+    }
+}
+
+// CPUが操作するプレイヤー
+// 設定されたプレイヤーは AiSystem が入力元を差し替える
+#[derive(Default)]
+pub struct AiSetting {
+    controllers: BTreeMap<PlayerTag, AiConfig>,
+}
+
+impl AiSetting {
+    pub fn set_ai(&mut self, tag: PlayerTag, config: AiConfig) {
+        log::info!("set ai: {:?} {:?}", tag, config);
+        self.controllers.insert(tag, config);
+    }
+
+    // 入力元はデバイス入力に戻る
+    pub fn remove_ai(&mut self, tag: PlayerTag) {
+        self.controllers.remove(&tag);
+    }
+
+    pub fn config(&self, tag: PlayerTag) -> Option<&AiConfig> {
+        self.controllers.get(&tag)
+    }
+
+    pub fn controllers(&self) -> impl Iterator<Item = (&PlayerTag, &AiConfig)> {
+        self.controllers.iter()
+    }
+}
+
+// 相手の技の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttackPhase {
+    None,
+    Startup,
+    Active,
+    Recovery,
+}
+
+// 1フレーム分の状況
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Observation {
+    pub(crate) distance: f32,
+    pub(crate) self_knockback: bool,
+    pub(crate) opponent_knockback: bool,
+    pub(crate) opponent_phase: AttackPhase,
+}
+
+// CPU1人分の状態
+#[derive(Debug, Clone)]
+pub(crate) struct AiController {
+    pub(crate) config: AiConfig,
+    pub(crate) observations: VecDeque<Observation>, // 反応遅延分の状況
+    pub(crate) plan: VecDeque<InputFlag>,           // これから入力する予定(右向き基準)
+    pub(crate) queued: InputFlag,                   // 次のフレームで読まれる入力(画面基準)
+}
+
+impl AiController {
+    pub(crate) fn new(config: AiConfig) -> Self {
+        AiController {
+            config,
+            observations: VecDeque::new(),
+            plan: VecDeque::new(),
+            queued: InputFlag::empty(),
+        }
+    }
+}
+
+// CPUの進行状態
+// ロールバックで巻き戻せるようにシステムではなくワールドに持たせる
+#[derive(Debug, Clone, Default)]
+pub struct AiState {
+    pub(crate) controllers: BTreeMap<PlayerTag, AiController>,
+}
+
+// CPUの入力元に入力を詰めるキュー
+// 詰めた入力は次のフレームで読まれるので，スナップショットの復元時に詰めなおす
+#[derive(Default)]
+pub(crate) struct AiInputQueues {
+    pub(crate) queues: BTreeMap<PlayerTag, InputQueue>,
+}
+
+impl AiInputQueues {
+    pub(crate) fn requeue(&self, state: &AiState) {
+        for (tag, queue) in &self.queues {
+            queue.clear();
+            if let Some(controller) = state.controllers.get(tag) {
+                queue.push(controller.queued);
+            }
+        }
+    }
+}
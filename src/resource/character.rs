@@ -0,0 +1,92 @@
+use crate::{
+    components::SkillSet,
+    id::{file::FileId, pack::PackKey},
+    paramater::{GaugeParamater, JumpParamater, MoveParamater},
+};
+use amethyst::{
+    assets::{Asset, Handle},
+    ecs::DenseVecStorage,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type CharacterDefinitionHandle = Handle<CharacterDefinition>;
+
+// キャラクターの定義(<名前>.chara.ron，例は tests/assets/sample.chara.ron)
+// コマンドリストは名前で参照するので別途読み込んでおく
+#[derive(Serialize, Deserialize)]
+pub struct CharacterDefinition {
+    file: FileId, // アニメーションファイル
+    pack: PackKey,
+    command: String, // コマンドリスト名
+    skill_set: SkillSet,
+    health: f32,
+    movement: MoveParamater,
+    jump: JumpParamater,
+    gauge: GaugeParamater,
+}
+
+impl Asset for CharacterDefinition {
+    const NAME: &'static str = "CHARACTER_DEFINITION";
+
+    type Data = Self;
+    type HandleStorage = DenseVecStorage<Handle<Self>>;
+}
+
+impl CharacterDefinition {
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    pub fn pack(&self) -> PackKey {
+        self.pack
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    pub fn skill_set(&self) -> &SkillSet {
+        &self.skill_set
+    }
+
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+
+    pub fn movement(&self) -> &MoveParamater {
+        &self.movement
+    }
+
+    pub fn jump(&self) -> &JumpParamater {
+        &self.jump
+    }
+
+    pub fn gauge(&self) -> &GaugeParamater {
+        &self.gauge
+    }
+}
+
+pub struct CharacterStore {
+    characters: BTreeMap<String, CharacterDefinitionHandle>,
+}
+
+impl CharacterStore {
+    pub fn new() -> Self {
+        CharacterStore {
+            characters: BTreeMap::new(),
+        }
+    }
+
+    pub fn character_names(&self) -> impl Iterator<Item = &String> {
+        self.characters.keys()
+    }
+
+    pub fn character(&self, key: &str) -> Option<&CharacterDefinitionHandle> {
+        self.characters.get(key)
+    }
+
+    pub fn add_character(&mut self, key: &str, character: CharacterDefinitionHandle) {
+        self.characters.insert(key.into(), character);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{flag::Cancel, paramater::HitLevel};
+
+// 攻撃同士がぶつかったときの勝敗の決め方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClashRule {
+    HitLevel, // ヒットレベルの高い方の攻撃が残る(同じなら双方消える)
+    Even,     // 常に双方の攻撃が消える
+}
+
+// 攻撃同士がぶつかったときの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClashResult {
+    Win,  // 自分の攻撃が残る
+    Lose, // 自分の攻撃が消える
+    Even, // 双方の攻撃が消える
+}
+
+// 攻撃判定同士の相殺の設定
+// 無効の場合は攻撃判定同士はぶつからない
+#[derive(Debug, Clone)]
+pub struct ClashSetting {
+    pub enabled: bool,
+    pub rule: ClashRule,
+    pub hitstop: usize,      // 相殺時に双方にかけるヒットストップ
+    pub cancel_frame: usize, // 相殺後にキャンセルできるフレーム数
+    pub cancel: Cancel,      // 相殺後にキャンセルできる行動
+}
+
+impl Default for ClashSetting {
+    fn default() -> Self {
+        ClashSetting {
+            enabled: false,
+            rule: ClashRule::HitLevel,
+            hitstop: 12,
+            cancel_frame: 10,
+            cancel: Cancel::SKILL,
+        }
+    }
+}
+
+impl ClashSetting {
+    // 自分の攻撃から見た相殺の結果
+    pub fn resolve(&self, level: HitLevel, other_level: HitLevel) -> ClashResult {
+        match self.rule {
+            ClashRule::HitLevel if level > other_level => ClashResult::Win,
+            ClashRule::HitLevel if level < other_level => ClashResult::Lose,
+            _ => ClashResult::Even,
+        }
+    }
+}
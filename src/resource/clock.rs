@@ -0,0 +1,67 @@
+// ゲーム進行の基準fps
+pub const SIMULATION_FPS: u32 = 60;
+
+// 1描画フレームで進める最大フレーム数
+// 処理落ち時に進めすぎないように制限する
+const MAX_STEPS: u32 = 4;
+
+// 浮動小数の誤差で1フレーム取りこぼさないための補正
+const STEP_EPSILON: f64 = 1e-6;
+
+// 描画フレームレートと切り離した固定fpsの進行管理
+// ゲーム進行のディスパッチは1回で1フレーム進む(Simulation が回数を決める)
+pub struct FrameClock {
+    fps: u32,
+    frame: u64,       // 開始からの進行フレーム数
+    accumulator: f64, // まだフレームとして消化していない時間
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        FrameClock::new(SIMULATION_FPS)
+    }
+}
+
+impl FrameClock {
+    pub fn new(fps: u32) -> Self {
+        FrameClock {
+            fps,
+            frame: 0,
+            accumulator: 0.,
+        }
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    // 1フレームの秒数
+    pub fn frame_seconds(&self) -> f32 {
+        1. / self.fps as f32
+    }
+
+    // 経過時間を積算して今回進めるフレーム数を返す
+    pub(crate) fn advance(&mut self, delta_seconds: f32) -> u32 {
+        let frame_time = 1. / self.fps as f64;
+        self.accumulator += delta_seconds as f64;
+
+        let steps = ((self.accumulator + STEP_EPSILON) / frame_time).floor() as u32;
+        self.accumulator = (self.accumulator - steps as f64 * frame_time).max(0.);
+
+        steps.min(MAX_STEPS)
+    }
+
+    // 1フレーム進める(ディスパッチの直前に呼ぶ)
+    pub(crate) fn tick(&mut self) {
+        self.frame += 1;
+    }
+
+    // ロールバック時にフレーム数を巻き戻す
+    pub(crate) fn set_frame(&mut self, frame: u64) {
+        self.frame = frame;
+    }
+}
@@ -63,6 +63,10 @@ impl CommandStore {
         self.command_lists.iter()
     }
 
+    pub fn command_names(&self) -> impl Iterator<Item = &String> {
+        self.command_lists.keys()
+    }
+
     pub fn commands(&self) -> impl Iterator<Item = &CommandListHandle> {
         self.command_lists.iter().map(|(_, handle)| handle)
     }
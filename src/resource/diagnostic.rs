@@ -0,0 +1,13 @@
+use amethyst::ecs::Entity;
+
+// 判定処理で起きた想定外の事象
+// 処理は継続するのでデバッグ用に購読する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HitDiagnostic {
+    // ヒットの種類が決まらない判定の組み合わせ
+    UnexpectedHitType {
+        entity1: Entity,
+        entity2: Entity,
+        description: String,
+    },
+}
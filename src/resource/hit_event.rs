@@ -0,0 +1,72 @@
+use crate::{
+    paramater::HitLevel,
+    resource::clash::ClashResult,
+    types::{DamageCollisionId, SparkKind},
+};
+use amethyst::ecs::Entity;
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+
+// 攻撃が当たったとき，攻撃同士が相殺したときのイベント
+// ヒット情報の適用時に発行するので，演出などはこれを購読する
+// 相殺は被弾とは別のイベントとして発行する(attacker は相殺した相手，damage は 0)
+pub struct HitEvent<T>
+where
+    T: AnimationFile,
+{
+    pub attacker: Entity, // 攻撃した相手(弾の場合は生成元)
+    pub defender: Entity,
+    pub collision_id: Option<DamageCollisionId<T>>,
+    pub hit_level: Option<HitLevel>,
+    pub damage: f32,
+    pub blocked: bool, // ガードされた(同フレームに受けた攻撃をすべてガードした場合のみ)
+    pub armored: bool, // アーマーで受け止められた
+    pub counter_hit: bool,
+    pub trade: bool,                  // 相打ち
+    pub clash: Option<ClashResult>,   // 相殺の結果(相殺のイベントのみ)
+    pub position: Option<(f32, f32)>, // 接触した位置(判定が重なった範囲の中心)
+    pub spark: Option<SparkKind>,
+}
+
+impl<T> Clone for HitEvent<T>
+where
+    T: AnimationFile,
+{
+    fn clone(&self) -> Self {
+        HitEvent {
+            attacker: self.attacker,
+            defender: self.defender,
+            collision_id: self.collision_id,
+            hit_level: self.hit_level,
+            damage: self.damage,
+            blocked: self.blocked,
+            armored: self.armored,
+            counter_hit: self.counter_hit,
+            trade: self.trade,
+            clash: self.clash,
+            position: self.position,
+            spark: self.spark,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for HitEvent<T>
+where
+    T: AnimationFile,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HitEvent")
+            .field("attacker", &self.attacker)
+            .field("defender", &self.defender)
+            .field("collision_id", &self.collision_id)
+            .field("hit_level", &self.hit_level)
+            .field("damage", &self.damage)
+            .field("blocked", &self.blocked)
+            .field("armored", &self.armored)
+            .field("counter_hit", &self.counter_hit)
+            .field("trade", &self.trade)
+            .field("clash", &self.clash)
+            .field("position", &self.position)
+            .field("spark", &self.spark)
+            .finish()
+    }
+}
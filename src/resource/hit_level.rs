@@ -0,0 +1,140 @@
+use crate::paramater::HitLevel;
+use amethyst::{
+    assets::{Asset, AssetStorage, Handle},
+    ecs::DenseVecStorage,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type HitLevelTableHandle = Handle<HitLevelTable>;
+
+// ヒットレベルごとの設定
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HitLevelEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attacker_hitstop: Option<usize>, // 攻撃した側のヒットストップ(省略時は受けた側と同じ)
+    pub defender_hitstop: usize, // 攻撃を受けた側のヒットストップ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hitstun: Option<usize>, // ヒット硬直(攻撃側で0にした場合に使う)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockstun: Option<usize>, // ガード硬直(攻撃側で指定がないか0にした場合に使う)
+    #[serde(default)]
+    pub pushback: f32, // ヒット，ガード時に硬直の間に押し返す距離
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_hitstop: Option<usize>, // カウンターヒット時に追加するヒットストップ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_damage_rate: Option<f32>, // カウンターヒット時のダメージ倍率
+}
+
+impl HitLevelEntry {
+    // テーブルに設定がない場合の値
+    pub fn from_level(level: HitLevel) -> Self {
+        HitLevelEntry {
+            attacker_hitstop: Some(level.attacker_hitstop()),
+            defender_hitstop: level.defender_hitstop(),
+            hitstun: None,
+            blockstun: None,
+            pushback: 0.,
+            counter_hitstop: None,
+            counter_damage_rate: None,
+        }
+    }
+
+    // 表がなければヒットレベルの既定値を使う
+    pub fn resolve(table: Option<&HitLevelTable>, level: HitLevel) -> Self {
+        table
+            .map(|table| table.entry(level))
+            .unwrap_or_else(|| HitLevelEntry::from_level(level))
+    }
+
+    pub fn attacker_hitstop(&self) -> usize {
+        self.attacker_hitstop.unwrap_or(self.defender_hitstop)
+    }
+
+    // 攻撃側で指定したヒット硬直(0なら表の値)
+    pub fn hitstun_frame(&self, frame: usize) -> usize {
+        if frame > 0 {
+            frame
+        } else {
+            self.hitstun.unwrap_or(0)
+        }
+    }
+
+    // 攻撃側で指定したガード硬直(指定がないか0なら表の値)
+    // どちらにもなければガードできない
+    pub fn blockstun_frame(&self, frame: Option<usize>) -> Option<usize> {
+        match frame {
+            Some(frame) if frame > 0 => Some(frame),
+            _ => self.blockstun.or(frame),
+        }
+    }
+}
+
+// ヒットレベルの設定表
+// 判定はヒットレベルの値でこの表を参照する
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HitLevelTable {
+    levels: BTreeMap<u8, HitLevelEntry>,
+}
+
+impl Asset for HitLevelTable {
+    const NAME: &'static str = "HIT_LEVEL_TABLE";
+
+    type Data = Self;
+    type HandleStorage = DenseVecStorage<Handle<Self>>;
+}
+
+impl HitLevelTable {
+    pub fn new() -> Self {
+        HitLevelTable {
+            levels: BTreeMap::new(),
+        }
+    }
+
+    pub fn levels(&self) -> impl Iterator<Item = (&u8, &HitLevelEntry)> {
+        self.levels.iter()
+    }
+
+    #[cfg(feature = "serialize")]
+    pub fn add_level(&mut self, level: u8, entry: HitLevelEntry) {
+        self.levels.insert(level, entry);
+    }
+
+    // Custom はヒットストップのみ自身の値を使う
+    pub fn entry(&self, level: HitLevel) -> HitLevelEntry {
+        let entry = match self.levels.get(&level.level()) {
+            Some(&entry) => entry,
+            None => return HitLevelEntry::from_level(level),
+        };
+        match level {
+            HitLevel::Custom { .. } => HitLevelEntry {
+                attacker_hitstop: Some(level.attacker_hitstop()),
+                defender_hitstop: level.defender_hitstop(),
+                ..entry
+            },
+            _ => entry,
+        }
+    }
+}
+
+// 読み込んだヒットレベルの設定表
+// ゲーム全体で1つだけ使う
+#[derive(Default)]
+pub struct HitLevelStore {
+    table: Option<HitLevelTableHandle>,
+}
+
+impl HitLevelStore {
+    pub fn set_table(&mut self, table: HitLevelTableHandle) {
+        self.table = Some(table);
+    }
+
+    pub fn table<'a>(&self, storage: &'a AssetStorage<HitLevelTable>) -> Option<&'a HitLevelTable> {
+        storage.get(self.table.as_ref()?)
+    }
+
+    // 読み込み前はヒットレベルの既定値を使う
+    pub fn entry(&self, storage: &AssetStorage<HitLevelTable>, level: HitLevel) -> HitLevelEntry {
+        HitLevelEntry::resolve(self.table(storage), level)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    components::{Direction, PlayerTag},
+    input::InputFlag,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::{self, Write},
+};
+
+// 入力履歴のデフォルトの保持数
+pub const DEFAULT_HISTORY_LENGTH: usize = 20;
+
+// 同じ入力が続いたフレーム数をまとめた履歴
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputHistoryEntry {
+    input: InputFlag,     // 画面基準
+    direction: Direction, // 入力時の向き
+    frames: usize,
+}
+
+impl InputHistoryEntry {
+    // 画面基準の入力
+    pub fn input(&self) -> InputFlag {
+        self.input
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    // 向き基準(右向き)の入力
+    pub fn relative_input(&self) -> InputFlag {
+        match self.direction {
+            Direction::Right => self.input,
+            Direction::Left => self.input.mirrored(),
+        }
+    }
+
+    // 入力が続いたフレーム数
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    // 向き基準のテンキー表記(ニュートラルは5)
+    pub fn notation(&self) -> String {
+        let input = self.relative_input();
+        let directions = InputFlag::UP
+            | InputFlag::DOWN
+            | InputFlag::RIGHT
+            | InputFlag::LEFT
+            | InputFlag::RIGHT_DOWN
+            | InputFlag::LEFT_DOWN
+            | InputFlag::RIGHT_UP
+            | InputFlag::LEFT_UP;
+        if input.intersects(directions) {
+            format!("{}", input)
+        } else {
+            format!("5{}", input)
+        }
+    }
+}
+
+impl std::fmt::Display for InputHistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} x{}", self.notation(), self.frames)
+    }
+}
+
+// プレイヤーごとの入力履歴
+// 表示(ゲーム内，ログ)はこのデータから生成する
+#[derive(Debug, Clone)]
+pub struct InputHistory {
+    length: usize,
+    histories: BTreeMap<PlayerTag, VecDeque<InputHistoryEntry>>,
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        InputHistory::new(DEFAULT_HISTORY_LENGTH)
+    }
+}
+
+impl InputHistory {
+    pub fn new(length: usize) -> Self {
+        InputHistory {
+            length,
+            histories: BTreeMap::new(),
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    // 保持数を変更(超えた古い履歴は破棄)
+    pub fn set_length(&mut self, length: usize) {
+        self.length = length;
+        for history in self.histories.values_mut() {
+            while history.len() > length {
+                history.pop_front();
+            }
+        }
+    }
+
+    // 古い順
+    pub fn entries(&self, tag: PlayerTag) -> impl Iterator<Item = &InputHistoryEntry> {
+        self.histories.get(&tag).into_iter().flat_map(|h| h.iter())
+    }
+
+    // 新しい順
+    pub fn latest_entries(&self, tag: PlayerTag) -> impl Iterator<Item = &InputHistoryEntry> {
+        self.histories
+            .get(&tag)
+            .into_iter()
+            .flat_map(|h| h.iter().rev())
+    }
+
+    // "6 x12, 3 x1, 2A x3" の形式(古い順)
+    pub fn format(&self, tag: PlayerTag) -> String {
+        self.entries(tag)
+            .map(|entry| entry.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // プレイヤーごとに1行で書き出す
+    pub fn dump<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for tag in self.histories.keys() {
+            writeln!(writer, "{:?}: {}", tag, self.format(*tag))?;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.histories.clear();
+    }
+
+    // 1フレーム分の入力を追加
+    // 向きが変わった場合は同じ入力でも別の履歴にする
+    pub(crate) fn push(&mut self, tag: PlayerTag, input: InputFlag, direction: Direction) {
+        let length = self.length;
+        let history = self.histories.entry(tag).or_insert_with(VecDeque::new);
+        match history.back_mut() {
+            Some(last) if last.input == input && last.direction == direction => last.frames += 1,
+            _ => {
+                history.push_back(InputHistoryEntry {
+                    input,
+                    direction,
+                    frames: 1,
+                });
+                while history.len() > length {
+                    history.pop_front();
+                }
+            }
+        }
+    }
+}
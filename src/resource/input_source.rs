@@ -0,0 +1,84 @@
+use crate::{components::PlayerTag, input::DeviceSource, traits::InputSource};
+use std::collections::BTreeMap;
+
+// 入力元を差し替えるシステム
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceOwner {
+    Training,
+    Ai,
+}
+
+// 各プレイヤーの入力元
+// 指定がないプレイヤーはデバイス入力
+// システムが差し替える場合は claim で使用中にし，先に使用中にしたシステムを優先する
+pub struct InputSources {
+    sources: BTreeMap<PlayerTag, Box<dyn InputSource>>,
+    owners: BTreeMap<PlayerTag, SourceOwner>,
+}
+
+impl Default for InputSources {
+    fn default() -> Self {
+        let mut sources = InputSources {
+            sources: BTreeMap::new(),
+            owners: BTreeMap::new(),
+        };
+        sources.set_source(PlayerTag::P1, DeviceSource);
+        sources.set_source(PlayerTag::P2, DeviceSource);
+        sources
+    }
+}
+
+impl InputSources {
+    // 使用中のシステムがあっても差し替える(使用中は解除される)
+    pub fn set_source<S>(&mut self, tag: PlayerTag, source: S)
+    where
+        S: InputSource,
+    {
+        log::info!("set input source: {:?}", tag);
+        self.owners.remove(&tag);
+        self.sources.insert(tag, Box::new(source));
+    }
+
+    // 他のシステムが使用中でなければ差し替えて使用中にする
+    // 差し替えたら true
+    pub fn claim<S>(&mut self, tag: PlayerTag, owner: SourceOwner, source: S) -> bool
+    where
+        S: InputSource,
+    {
+        match self.owners.get(&tag) {
+            Some(&current) if current != owner => false,
+            _ => {
+                self.set_source(tag, source);
+                self.owners.insert(tag, owner);
+                true
+            }
+        }
+    }
+
+    // 使用中のシステムが手放したらデバイス入力に戻す
+    pub fn release(&mut self, tag: PlayerTag, owner: SourceOwner) {
+        if self.owners.get(&tag) == Some(&owner) {
+            self.reset_source(tag);
+        }
+    }
+
+    pub fn owner(&self, tag: PlayerTag) -> Option<SourceOwner> {
+        self.owners.get(&tag).cloned()
+    }
+
+    // デバイス入力に戻す
+    pub fn reset_source(&mut self, tag: PlayerTag) {
+        self.set_source(tag, DeviceSource);
+    }
+
+    pub fn remove_source(&mut self, tag: PlayerTag) {
+        self.owners.remove(&tag);
+        self.sources.remove(&tag);
+    }
+
+    pub(crate) fn sources_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&PlayerTag, &mut Box<dyn InputSource>)> {
+        self.sources.iter_mut()
+    }
+}
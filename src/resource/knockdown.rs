@@ -0,0 +1,36 @@
+use crate::paramater::Knockdown;
+
+// ダウン，起き上がり，受け身のフレーム設定
+#[derive(Debug, Clone)]
+pub struct KnockdownRule {
+    pub soft_down_frame: usize,  // ソフトダウンで寝ているフレーム数
+    pub hard_down_frame: usize,  // ハードダウンで寝ているフレーム数
+    pub wake_up_frame: usize,    // 起き上がりのフレーム数(無敵)
+    pub tech_roll_frame: usize,  // 受け身のフレーム数(無敵)
+    pub tech_window: usize,      // 着地後に受け身を受け付けるフレーム数
+    pub tech_input_frame: usize, // 受け身の先行入力を受け付けるフレーム数
+    pub bounce_frame: usize,     // バウンドで追加されるノックバックフレーム数
+}
+
+impl Default for KnockdownRule {
+    fn default() -> Self {
+        KnockdownRule {
+            soft_down_frame: 30,
+            hard_down_frame: 60,
+            wake_up_frame: 20,
+            tech_roll_frame: 24,
+            tech_window: 10,
+            tech_input_frame: 6,
+            bounce_frame: 20,
+        }
+    }
+}
+
+impl KnockdownRule {
+    pub fn down_frame(&self, knockdown: Knockdown) -> usize {
+        match knockdown {
+            Knockdown::Soft => self.soft_down_frame,
+            Knockdown::Hard => self.hard_down_frame,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{components::PlayerTag, resource::clock::SIMULATION_FPS};
+use std::collections::BTreeMap;
+
+// 引き分けが続いた場合に延長するラウンド数の初期値
+const DEFAULT_EXTRA_ROUNDS: usize = 2;
+
+// 試合のルール
+#[derive(Debug, Clone)]
+pub struct MatchRule {
+    pub rounds_to_win: usize, // 勝利に必要なラウンド数
+    pub extra_rounds: usize,  // 引き分けが続いた場合に延長するラウンド数
+    pub round_frames: u64,    // 1ラウンドの制限時間
+    pub intro_frames: u64,    // ラウンド開始演出(入力不可)
+    pub outro_frames: u64,    // ラウンド終了演出(入力不可)
+    pub start_positions: BTreeMap<PlayerTag, (f32, f32)>, // ラウンド開始時の位置
+}
+
+impl Default for MatchRule {
+    fn default() -> Self {
+        MatchRule::best_of(3)
+    }
+}
+
+impl MatchRule {
+    // 3本先取なら best_of(5)
+    pub fn best_of(rounds: usize) -> Self {
+        let fps = SIMULATION_FPS as u64;
+        MatchRule {
+            rounds_to_win: rounds / 2 + 1,
+            extra_rounds: DEFAULT_EXTRA_ROUNDS,
+            round_frames: 99 * fps,
+            intro_frames: 2 * fps,
+            outro_frames: 3 * fps,
+            start_positions: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_extra_rounds(mut self, extra_rounds: usize) -> Self {
+        self.extra_rounds = extra_rounds;
+        self
+    }
+
+    // 引き分けが続いた場合の最大ラウンド数
+    pub fn max_rounds(&self) -> usize {
+        self.rounds_to_win * 2 - 1 + self.extra_rounds
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchPhase {
+    Idle,     // 試合をしていない(入力制限なし)
+    Intro,    // ラウンド開始演出
+    Fight,    // 対戦中
+    Outro,    // ラウンド終了演出
+    Finished, // 試合終了
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinReason {
+    Ko,
+    TimeOut, // 残り体力の多い方が勝利
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundResult {
+    Win {
+        winner: PlayerTag,
+        reason: WinReason,
+    },
+    DoubleKo,
+    Draw, // タイムアップ時に体力が同じ
+}
+
+// 試合進行のイベント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchEvent {
+    RoundStart { round: usize },
+    Fight { round: usize },
+    RoundEnd { round: usize, result: RoundResult },
+    MatchEnd { winner: Option<PlayerTag> },
+}
+
+// 試合の進行状況
+#[derive(Debug, Clone)]
+pub struct MatchState {
+    rule: MatchRule,
+    pub(crate) phase: MatchPhase,
+    pub(crate) round: usize,
+    pub(crate) phase_frame: u64, // 現在のフェーズの経過フレーム
+    pub(crate) rest_frame: u64,  // ラウンドの残り時間
+    pub(crate) wins: BTreeMap<PlayerTag, usize>,
+    pub(crate) results: Vec<RoundResult>,
+    pub(crate) reset_requested: bool,
+}
+
+impl Default for MatchState {
+    fn default() -> Self {
+        MatchState::new(MatchRule::default())
+    }
+}
+
+impl MatchState {
+    pub fn new(rule: MatchRule) -> Self {
+        MatchState {
+            rest_frame: rule.round_frames,
+            rule,
+            phase: MatchPhase::Idle,
+            round: 0,
+            phase_frame: 0,
+            wins: BTreeMap::new(),
+            results: vec![],
+            reset_requested: false,
+        }
+    }
+
+    pub fn rule(&self) -> &MatchRule {
+        &self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: MatchRule) {
+        self.rule = rule;
+    }
+
+    // 試合開始(1ラウンド目の開始演出から)
+    pub fn start(&mut self) {
+        log::info!("match start: {:?}", self.rule);
+        self.round = 0;
+        self.wins.clear();
+        self.results.clear();
+        self.phase = MatchPhase::Idle;
+        self.reset_requested = true;
+    }
+
+    // 試合を止める
+    pub fn stop(&mut self) {
+        self.phase = MatchPhase::Idle;
+        self.reset_requested = false;
+    }
+
+    pub fn phase(&self) -> MatchPhase {
+        self.phase
+    }
+
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn rest_frame(&self) -> u64 {
+        self.rest_frame
+    }
+
+    // 表示用の残り秒数(切り上げ)
+    pub fn rest_seconds(&self) -> u64 {
+        let fps = SIMULATION_FPS as u64;
+        (self.rest_frame + fps - 1) / fps
+    }
+
+    pub fn wins(&self, tag: PlayerTag) -> usize {
+        self.wins.get(&tag).cloned().unwrap_or(0)
+    }
+
+    pub fn results(&self) -> &[RoundResult] {
+        &self.results
+    }
+
+    // 演出中は入力を受け付けない
+    pub fn is_input_locked(&self) -> bool {
+        match self.phase {
+            MatchPhase::Intro | MatchPhase::Outro | MatchPhase::Finished => true,
+            MatchPhase::Idle | MatchPhase::Fight => false,
+        }
+    }
+
+    // 試合終了時の勝者(引き分けは None)
+    pub fn winner(&self) -> Option<PlayerTag> {
+        if self.phase != MatchPhase::Finished {
+            return None;
+        }
+        let mut wins = self.wins.iter().collect::<Vec<_>>();
+        wins.sort_by(|a, b| b.1.cmp(a.1));
+        match (wins.get(0), wins.get(1)) {
+            (Some((tag, first)), Some((_, second))) if first > second => Some(**tag),
+            (Some((tag, _)), None) => Some(**tag),
+            _ => None,
+        }
+    }
+}
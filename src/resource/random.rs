@@ -0,0 +1,49 @@
+use crate::{components::PlayerTag, types::random::XorShift};
+use std::collections::BTreeMap;
+
+// 乱数の用途
+// 用途ごとに系列を分けて，片方のシード変更がもう片方に影響しないようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RandomStream {
+    Training,
+    Ai(PlayerTag),
+}
+
+// ゲーム進行に使う乱数の状態
+// ロールバックで巻き戻せるようにシステムではなくワールドに持たせる
+#[derive(Debug, Clone, Default)]
+pub struct GameRandom {
+    streams: BTreeMap<RandomStream, (u64, XorShift)>,
+}
+
+impl GameRandom {
+    // シードを指定して作り直す
+    pub fn reset(&mut self, stream: RandomStream, seed: u64) {
+        self.streams.insert(stream, (seed, XorShift::new(seed)));
+    }
+
+    // シードが変わったときだけ作り直す
+    pub fn reseed(&mut self, stream: RandomStream, seed: u64) {
+        let changed = self
+            .streams
+            .get(&stream)
+            .map(|(current, _)| *current != seed)
+            .unwrap_or(true);
+        if changed == true {
+            self.reset(stream, seed);
+        }
+    }
+
+    // 未設定の系列はシード 0 で作る
+    pub fn get_mut(&mut self, stream: RandomStream) -> &mut XorShift {
+        &mut self
+            .streams
+            .entry(stream)
+            .or_insert_with(|| (0, XorShift::new(0)))
+            .1
+    }
+
+    pub(crate) fn streams(&self) -> impl Iterator<Item = (&RandomStream, &(u64, XorShift))> {
+        self.streams.iter()
+    }
+}
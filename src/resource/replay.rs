@@ -0,0 +1,185 @@
+use crate::{
+    components::PlayerTag, id::file::FileId, input::InputSignal, resource::command::CommandStore,
+};
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+// リプレイファイルのフォーマットバージョン
+// 保存内容を変更した場合は上げる
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "unsupported replay version: {} (current {})", _0, _1)]
+    UnsupportedVersion(u32, u32),
+}
+
+// 1フレーム分の全プレイヤーの入力
+pub type ReplayFrame = BTreeMap<PlayerTag, InputSignal>;
+
+// リプレイとして保存する情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayData {
+    version: u32,
+    seed: u64,                               // 乱数シード
+    characters: BTreeMap<PlayerTag, FileId>, // 各プレイヤーのキャラクター
+    commands: Vec<String>,                   // 読み込んでいたコマンドリスト
+    frames: Vec<ReplayFrame>,                // フレームごとの入力
+}
+
+impl ReplayData {
+    pub fn new(seed: u64, characters: BTreeMap<PlayerTag, FileId>, commands: Vec<String>) -> Self {
+        ReplayData {
+            version: REPLAY_VERSION,
+            seed,
+            characters,
+            commands,
+            frames: vec![],
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn characters(&self) -> impl Iterator<Item = (&PlayerTag, &FileId)> {
+        self.characters.iter()
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &String> {
+        self.commands.iter()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn frame(&self, frame: usize) -> Option<&ReplayFrame> {
+        self.frames.get(frame)
+    }
+
+    pub(crate) fn push_frame(&mut self, frame: ReplayFrame) {
+        self.frames.push(frame);
+    }
+
+    pub fn save<P>(&self, path: P) -> Result<(), failure::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let serialized = ron::ser::to_string(self)?;
+        std::fs::write(path, serialized)?;
+        Ok(())
+    }
+
+    pub fn load<P>(path: P) -> Result<Self, failure::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let serialized = std::fs::read_to_string(path)?;
+        let data: ReplayData = ron::de::from_str(&serialized)?;
+        if data.version != REPLAY_VERSION {
+            return Err(Error::UnsupportedVersion(data.version, REPLAY_VERSION).into());
+        }
+        Ok(data)
+    }
+}
+
+enum ReplayState {
+    Idle,
+    Recording(ReplayData),
+    Playback { data: ReplayData, frame: usize },
+}
+
+// 入力の記録，再生状態を管理するリソース
+// 再生中はデバイスの入力の代わりに記録した入力をバッファに詰める
+pub struct InputReplay {
+    state: ReplayState,
+}
+
+impl Default for InputReplay {
+    fn default() -> Self {
+        InputReplay {
+            state: ReplayState::Idle,
+        }
+    }
+}
+
+impl InputReplay {
+    pub fn start_recording(
+        &mut self,
+        seed: u64,
+        characters: BTreeMap<PlayerTag, FileId>,
+        store: &CommandStore,
+    ) {
+        let commands = store.command_names().cloned().collect();
+        log::info!("start recording: seed = {}", seed);
+        self.state = ReplayState::Recording(ReplayData::new(seed, characters, commands));
+    }
+
+    // 記録を終了して記録したデータを返す
+    pub fn stop_recording(&mut self) -> Option<ReplayData> {
+        match std::mem::replace(&mut self.state, ReplayState::Idle) {
+            ReplayState::Recording(data) => {
+                log::info!("stop recording: {} F", data.frame_count());
+                Some(data)
+            }
+            state => {
+                self.state = state;
+                None
+            }
+        }
+    }
+
+    pub fn start_playback(&mut self, data: ReplayData) {
+        log::info!("start playback: {} F", data.frame_count());
+        self.state = ReplayState::Playback { data, frame: 0 };
+    }
+
+    pub fn stop_playback(&mut self) {
+        if self.is_playback() == true {
+            self.state = ReplayState::Idle;
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        match self.state {
+            ReplayState::Recording(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_playback(&self) -> bool {
+        match self.state {
+            ReplayState::Playback { .. } => true,
+            _ => false,
+        }
+    }
+
+    // 再生中なら次フレームの入力を返す
+    // 最後まで再生したら再生を終了する
+    pub(crate) fn next_frame(&mut self) -> Option<ReplayFrame> {
+        let next = match &mut self.state {
+            ReplayState::Playback { data, frame } => {
+                let next = data.frame(*frame).cloned();
+                *frame += 1;
+                next
+            }
+            _ => return None,
+        };
+
+        if next.is_none() {
+            log::info!("playback finished");
+            self.state = ReplayState::Idle;
+        }
+
+        next
+    }
+
+    // 記録中ならフレームの入力を記録する
+    pub(crate) fn record(&mut self, frame: &ReplayFrame) {
+        if let ReplayState::Recording(data) = &mut self.state {
+            data.push_frame(frame.clone());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// 壁の位置の初期値(画面中央が原点)
+const DEFAULT_WALLS: (f32, f32) = (-640., 640.);
+
+// ステージの範囲
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub walls: Option<(f32, f32)>, // 左右の壁の位置(None なら壁なし)
+}
+
+impl Default for Stage {
+    fn default() -> Self {
+        Stage::new(DEFAULT_WALLS.0, DEFAULT_WALLS.1)
+    }
+}
+
+impl Stage {
+    pub fn new(left: f32, right: f32) -> Self {
+        Stage {
+            walls: Some((left, right)),
+        }
+    }
+
+    // 壁のないステージ
+    pub fn unbounded() -> Self {
+        Stage { walls: None }
+    }
+
+    // 壁に接しているか
+    pub fn is_wall(&self, x: f32) -> bool {
+        self.walls
+            .map(|(left, right)| x <= left || x >= right)
+            .unwrap_or(false)
+    }
+
+    // 壁の内側に収める
+    pub fn clamp(&self, x: f32) -> f32 {
+        self.walls
+            .map(|(left, right)| x.max(left).min(right))
+            .unwrap_or(x)
+    }
+
+    // 壁の外側か
+    pub fn is_outside(&self, x: f32) -> bool {
+        self.walls
+            .map(|(left, right)| x < left || x > right)
+            .unwrap_or(false)
+    }
+}
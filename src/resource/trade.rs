@@ -0,0 +1,21 @@
+// 相打ち(同じフレームに互いの攻撃が当たった場合)の設定
+// 何も有効にしなければ双方がダメージとノックバックを受ける
+// カウンターヒットの値はヒットレベルの設定表にあればそちらを使う
+#[derive(Debug, Clone)]
+pub struct TradeRule {
+    pub hit_level_priority: bool, // ヒットレベルの高い攻撃だけが当たる(同じなら双方当たる)
+    pub counter_hit: bool,        // 双方カウンターヒット扱いにする
+    pub counter_damage_rate: f32, // カウンターヒット時のダメージ倍率
+    pub counter_hitstop: usize,   // カウンターヒット時に追加するヒットストップ
+}
+
+impl Default for TradeRule {
+    fn default() -> Self {
+        TradeRule {
+            hit_level_priority: false,
+            counter_hit: false,
+            counter_damage_rate: 1.25,
+            counter_hitstop: 6,
+        }
+    }
+}
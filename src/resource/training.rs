@@ -0,0 +1,219 @@
+use crate::{components::PlayerTag, input::InputFlag};
+
+// トレーニングダミーの姿勢
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DummyStance {
+    Stand,
+    Crouch,
+    Jump,
+}
+
+// トレーニングダミーのガード設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DummyGuard {
+    None,
+    All,           // すべてガード
+    AfterFirstHit, // 連続技の初段を受けた後はガード
+    Random,        // ヒットするたびにガードするかランダムで決める
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecordState {
+    Idle,
+    Recording,                        // 操作側の入力でダミーを動かして記録
+    Playback { loop_playback: bool }, // 記録した入力をダミーが再生
+}
+
+// トレーニングモードの設定
+// メニューなどからこのリソースを書き換えてダミーの挙動を変更する
+#[derive(Debug, Clone)]
+pub struct TrainingSetting {
+    enabled: bool,
+    dummy: PlayerTag,      // ダミーとして動かすプレイヤー
+    controller: PlayerTag, // 操作するプレイヤー(記録時はダミーを操作する)
+    stance: DummyStance,
+    guard: DummyGuard,
+    guard_random_rate: f32,   // ランダムガード時にガードする確率
+    reversal: Vec<InputFlag>, // 起き上がり(やられから復帰した直後)に入力する
+    health_refill: bool,
+    refill_delay: usize, // 双方がやられ状態でなくなってから体力を回復するまでのフレーム数
+    gauge_refill: bool,
+    seed: u64,
+    pub(crate) record_state: RecordState,
+    pub(crate) recorded: Vec<InputFlag>, // ダミーが右向きの場合の入力で記録
+}
+
+impl Default for TrainingSetting {
+    fn default() -> Self {
+        TrainingSetting {
+            enabled: false,
+            dummy: PlayerTag::P2,
+            controller: PlayerTag::P1,
+            stance: DummyStance::Stand,
+            guard: DummyGuard::None,
+            guard_random_rate: 0.5,
+            reversal: vec![],
+            health_refill: true,
+            refill_delay: 60,
+            gauge_refill: false,
+            seed: 0,
+            record_state: RecordState::Idle,
+            recorded: vec![],
+        }
+    }
+}
+
+impl TrainingSetting {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        log::info!("training mode: {}", enabled);
+        self.enabled = enabled;
+    }
+
+    pub fn dummy(&self) -> PlayerTag {
+        self.dummy
+    }
+
+    pub fn controller(&self) -> PlayerTag {
+        self.controller
+    }
+
+    // ダミーと操作側のプレイヤーを設定
+    pub fn set_players(&mut self, dummy: PlayerTag, controller: PlayerTag) {
+        self.dummy = dummy;
+        self.controller = controller;
+    }
+
+    pub fn stance(&self) -> DummyStance {
+        self.stance
+    }
+
+    pub fn set_stance(&mut self, stance: DummyStance) {
+        self.stance = stance;
+    }
+
+    pub fn guard(&self) -> DummyGuard {
+        self.guard
+    }
+
+    pub fn set_guard(&mut self, guard: DummyGuard) {
+        self.guard = guard;
+    }
+
+    pub fn guard_random_rate(&self) -> f32 {
+        self.guard_random_rate
+    }
+
+    pub fn set_guard_random_rate(&mut self, rate: f32) {
+        self.guard_random_rate = rate.max(0.).min(1.);
+    }
+
+    pub fn reversal(&self) -> &[InputFlag] {
+        &self.reversal
+    }
+
+    // 復帰時に入力する技(右向き基準の入力)
+    // 空なら何もしない
+    pub fn set_reversal(&mut self, inputs: Vec<InputFlag>) {
+        self.reversal = inputs;
+    }
+
+    pub fn health_refill(&self) -> bool {
+        self.health_refill
+    }
+
+    pub fn set_health_refill(&mut self, refill: bool) {
+        self.health_refill = refill;
+    }
+
+    pub fn refill_delay(&self) -> usize {
+        self.refill_delay
+    }
+
+    pub fn set_refill_delay(&mut self, frames: usize) {
+        self.refill_delay = frames;
+    }
+
+    pub fn gauge_refill(&self) -> bool {
+        self.gauge_refill
+    }
+
+    pub fn set_gauge_refill(&mut self, refill: bool) {
+        self.gauge_refill = refill;
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // ランダムガード用の乱数のシード
+    // 変更時に乱数は初期化される
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    // 操作側でダミーを動かして入力を記録する
+    pub fn start_recording(&mut self) {
+        log::info!("training record start");
+        self.recorded.clear();
+        self.record_state = RecordState::Recording;
+    }
+
+    pub fn stop_recording(&mut self) {
+        if self.is_recording() {
+            log::info!("training record stop: {} F", self.recorded.len());
+            self.record_state = RecordState::Idle;
+        }
+    }
+
+    pub fn start_playback(&mut self, loop_playback: bool) {
+        log::info!("training playback start: {} F", self.recorded.len());
+        self.record_state = RecordState::Playback { loop_playback };
+    }
+
+    pub fn stop_playback(&mut self) {
+        if self.is_playback() {
+            self.record_state = RecordState::Idle;
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.record_state == RecordState::Recording
+    }
+
+    pub fn is_playback(&self) -> bool {
+        match self.record_state {
+            RecordState::Playback { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn recorded(&self) -> &[InputFlag] {
+        &self.recorded
+    }
+
+    pub fn set_recorded(&mut self, inputs: Vec<InputFlag>) {
+        self.recorded = inputs;
+    }
+}
+
+// トレーニングダミーの進行状態
+// ロールバックで巻き戻せるようにシステムではなくワールドに持たせる
+#[derive(Debug, Clone, Default)]
+pub struct TrainingDummyState {
+    pub(crate) was_knockback: bool,
+    pub(crate) neutral_frame: usize, // ダミーと操作側の両方がやられ状態でないフレーム数
+    pub(crate) combo_guard: bool,
+    pub(crate) random_guard: bool,
+    pub(crate) reversal_frame: Option<usize>,
+    pub(crate) playback_frame: usize,
+}
+
+impl TrainingDummyState {
+    pub(crate) fn reset(&mut self) {
+        *self = TrainingDummyState::default();
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{components::PlayerTag, flag::Cancel, paramater::HitLevel};
+use std::collections::BTreeMap;
+
+// トレーニング表示用のプレイヤーごとの情報
+#[derive(Debug, Clone, Default)]
+pub struct PlayerHud {
+    pub(crate) last_damage: Option<f32>,
+    pub(crate) last_hit_level: Option<HitLevel>,
+    pub(crate) combo_count: usize,
+    pub(crate) combo_damage: f32,
+    pub(crate) in_combo: bool,
+    pub(crate) hitstun: usize,
+    pub(crate) cancel: Cancel,
+    pub(crate) actionable: bool,
+    pub(crate) frame_advantage: Option<isize>,
+}
+
+impl PlayerHud {
+    // 最後に受けた攻撃のダメージ
+    pub fn last_damage(&self) -> Option<f32> {
+        self.last_damage
+    }
+
+    pub fn last_hit_level(&self) -> Option<HitLevel> {
+        self.last_hit_level
+    }
+
+    // 受けている(最後に受けた)連続技のヒット数
+    pub fn combo_count(&self) -> usize {
+        self.combo_count
+    }
+
+    pub fn combo_damage(&self) -> f32 {
+        self.combo_damage
+    }
+
+    pub fn is_in_combo(&self) -> bool {
+        self.in_combo
+    }
+
+    // 残りのやられフレーム
+    pub fn hitstun(&self) -> usize {
+        self.hitstun
+    }
+
+    // 現在のフレームでキャンセル可能な行動
+    pub fn cancel(&self) -> Cancel {
+        self.cancel
+    }
+
+    // やられ中でなく，ニュートラルに戻れる状態
+    pub fn is_actionable(&self) -> bool {
+        self.actionable
+    }
+
+    // 最後の攻防での硬直差(正なら有利)
+    pub fn frame_advantage(&self) -> Option<isize> {
+        self.frame_advantage
+    }
+}
+
+// 硬直差計測中の攻防
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Exchange {
+    pub(crate) attacker: PlayerTag,
+    pub(crate) defender: PlayerTag,
+    pub(crate) attacker_free: Option<u64>, // 攻撃側が動けるようになったフレーム
+    pub(crate) defender_free: Option<u64>, // 防御側が動けるようになったフレーム
+}
+
+// トレーニング表示用の情報
+// デバッグ表示やテストから参照する
+#[derive(Debug, Default)]
+pub struct TrainingHud {
+    pub(crate) players: BTreeMap<PlayerTag, PlayerHud>,
+    pub(crate) exchange: Option<Exchange>,
+}
+
+impl TrainingHud {
+    pub fn player(&self, tag: PlayerTag) -> Option<&PlayerHud> {
+        self.players.get(&tag)
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = (&PlayerTag, &PlayerHud)> {
+        self.players.iter()
+    }
+
+    // 硬直差を計測中か
+    pub fn is_measuring(&self) -> bool {
+        self.exchange.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.players.clear();
+        self.exchange = None;
+    }
+}
@@ -0,0 +1,89 @@
+use crate::resource::clock::FrameClock;
+use amethyst::{
+    core::{ArcThreadPool, SystemBundle, Time},
+    ecs::{Dispatcher, DispatcherBuilder, World, WorldExt},
+};
+
+// ゲーム進行を行うディスパッチャを構築する
+// 判定，アニメーションを含むゲーム進行のバンドルはすべてこちらに追加し，
+// 描画用のディスパッチとは分けておく
+pub struct SimulationBuilder<'a, 'b> {
+    builder: DispatcherBuilder<'a, 'b>,
+}
+
+impl<'a, 'b> SimulationBuilder<'a, 'b> {
+    pub fn new() -> Self {
+        SimulationBuilder {
+            builder: DispatcherBuilder::new(),
+        }
+    }
+
+    pub fn with_pool(mut self, pool: ArcThreadPool) -> Self {
+        self.builder = self.builder.with_pool(pool);
+        self
+    }
+
+    pub fn with_bundle<B>(mut self, world: &mut World, bundle: B) -> amethyst::Result<Self>
+    where
+        B: SystemBundle<'a, 'b>,
+    {
+        bundle.build(world, &mut self.builder)?;
+        Ok(self)
+    }
+
+    pub fn build(self, world: &mut World) -> Simulation<'a, 'b> {
+        let mut dispatcher = self.builder.build();
+        dispatcher.setup(world);
+
+        Simulation { dispatcher }
+    }
+}
+
+// 固定fpsでゲーム進行のディスパッチを行う
+// 1回のディスパッチで1フレーム進むので，描画フレームレートに関係なく同じ結果になる
+// State の update などから描画フレーム毎に update を呼ぶ
+pub struct Simulation<'a, 'b> {
+    dispatcher: Dispatcher<'a, 'b>,
+}
+
+impl<'a, 'b> Simulation<'a, 'b> {
+    // 描画フレームの経過時間分だけフレームを進める
+    // 進めたフレーム数を返す
+    pub fn update(&mut self, world: &mut World) -> u32 {
+        #[cfg(not(feature = "count-frame"))]
+        let steps = {
+            let delta = world.read_resource::<Time>().delta_seconds();
+            world.write_resource::<FrameClock>().advance(delta)
+        };
+        #[cfg(feature = "count-frame")]
+        let steps = 1;
+
+        for _ in 0..steps {
+            self.step(world);
+        }
+        steps
+    }
+
+    // 経過時間に関係なく1フレーム進める
+    // テストやロールバックの再シミュレーションで使う
+    pub fn step(&mut self, world: &mut World) {
+        let frame_seconds = {
+            let mut clock = world.write_resource::<FrameClock>();
+            clock.tick();
+            clock.frame_seconds()
+        };
+
+        // アニメーションなど経過時間で進む処理も1フレーム分だけ進める
+        let delta = {
+            let mut time = world.write_resource::<Time>();
+            let delta = time.delta_seconds();
+            time.set_delta_seconds(frame_seconds);
+            delta
+        };
+
+        self.dispatcher.dispatch(world);
+        world.maintain();
+
+        world.write_resource::<Time>().set_delta_seconds(delta);
+    }
+}
@@ -0,0 +1,680 @@
+use crate::{
+    components::{
+        ActiveCommand, ArmorCount, ClashCancel, Damaged, Direction, Down, Gauge, Health, HitInfo,
+        HitShake, Hitstop, Jump, Knockback, PlayerTag, Projectile, SkillCount, SpawnHistory,
+    },
+    input::FightInput,
+    resource::{
+        ai::{AiInputQueues, AiState},
+        clash::ClashSetting,
+        clock::FrameClock,
+        input_history::InputHistory,
+        match_state::MatchState,
+        random::GameRandom,
+        trade::TradeRule,
+        training::{TrainingDummyState, TrainingSetting},
+    },
+    types::DamageCollisionId,
+};
+use amethyst::{
+    core::Transform,
+    ecs::{
+        Component, Entities, Entity, Join, Read, ReadExpect, ReadStorage, World, WorldExt, Write,
+        WriteExpect, WriteStorage,
+    },
+    utils::circular_buffer::CircularBuffer,
+};
+use amethyst_sprite_studio::{
+    components::{AnimationTime, PlayAnimationKey},
+    traits::animation_file::AnimationFile,
+};
+use input_handle::traits::InputParser;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, VecDeque},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+type Signal<'a> = <FightInput as InputParser<'a>>::InputSignal;
+
+// 弾から弾を生成した場合に生成元をたどる最大数
+const MAX_SPAWN_DEPTH: usize = 8;
+
+// 1エンティティ分のゲーム進行に関わるコンポーネント
+struct EntitySnapshot<T>
+where
+    T: AnimationFile,
+{
+    entity: Entity,
+    tag: Option<PlayerTag>,
+    transform: Option<Transform>,
+    direction: Option<Direction>,
+    knockback: Option<Knockback>,
+    down: Option<Down>,
+    jump: Option<Jump>,
+    damaged: Option<Damaged<T>>,
+    skill_count: Option<SkillCount<T>>,
+    armor_count: Option<ArmorCount<T>>,
+    spawn_history: Option<SpawnHistory<T>>,
+    active_command: Option<ActiveCommand>,
+    hit_info: Option<HitInfo<T>>,
+    clash_cancel: Option<ClashCancel>,
+    hit_shake: Option<HitShake>,
+    hitstop: Option<Hitstop>,
+    health: Option<Health>,
+    gauge: Option<Gauge>,
+    projectile: Option<Projectile>,
+    animation_time: Option<AnimationTime>,
+    animation_key: Option<PlayAnimationKey<T>>,
+}
+
+// ゲーム進行に関わるリソース
+struct ResourceSnapshot {
+    match_state: MatchState,
+    clash: ClashSetting,
+    trade: TradeRule,
+    random: GameRandom,
+    training: TrainingSetting, // 記録，再生の状態が進行で変わる
+    training_dummy: TrainingDummyState,
+    ai: AiState,
+    input_history: InputHistory, // 表示用なのでチェックサムには含めない
+}
+
+// ロールバック用のゲーム状態のスナップショット
+// アニメーションを再生しているエンティティを対象に保存する
+pub struct GameSnapshot<T>
+where
+    T: AnimationFile,
+{
+    frame: u64,
+    entities: Vec<EntitySnapshot<T>>,
+    resources: ResourceSnapshot,
+    inputs: Vec<Signal<'static>>,
+    checksum: u64,
+}
+
+impl<T> GameSnapshot<T>
+where
+    T: AnimationFile,
+{
+    pub fn capture(world: &mut World) -> Self {
+        let (frame, entities, inputs) = world.exec(
+            |(
+                entities,
+                clock,
+                buffer,
+                tags,
+                transforms,
+                directions,
+                knockbacks,
+                downs,
+                jumps,
+                damaged,
+                skill_counts,
+                armor_counts,
+                spawn_histories,
+                active_commands,
+                hit_infos,
+                clash_cancels,
+                hit_shakes,
+                hitstops,
+                healths,
+                gauges,
+                projectiles,
+                times,
+                keys,
+            ): (
+                Entities,
+                Read<FrameClock>,
+                ReadExpect<CircularBuffer<Signal<'static>>>,
+                ReadStorage<PlayerTag>,
+                ReadStorage<Transform>,
+                ReadStorage<Direction>,
+                ReadStorage<Knockback>,
+                ReadStorage<Down>,
+                ReadStorage<Jump>,
+                ReadStorage<Damaged<T>>,
+                ReadStorage<SkillCount<T>>,
+                ReadStorage<ArmorCount<T>>,
+                ReadStorage<SpawnHistory<T>>,
+                ReadStorage<ActiveCommand>,
+                ReadStorage<HitInfo<T>>,
+                ReadStorage<ClashCancel>,
+                ReadStorage<HitShake>,
+                ReadStorage<Hitstop>,
+                ReadStorage<Health>,
+                ReadStorage<Gauge>,
+                ReadStorage<Projectile>,
+                ReadStorage<AnimationTime>,
+                ReadStorage<PlayAnimationKey<T>>,
+            )| {
+                let entities = (&*entities, &keys)
+                    .join()
+                    .map(|(e, key)| EntitySnapshot {
+                        entity: e,
+                        tag: tags.get(e).cloned(),
+                        transform: transforms.get(e).cloned(),
+                        direction: directions.get(e).cloned(),
+                        knockback: knockbacks.get(e).cloned(),
+                        down: downs.get(e).cloned(),
+                        jump: jumps.get(e).cloned(),
+                        damaged: damaged.get(e).cloned(),
+                        skill_count: skill_counts.get(e).cloned(),
+                        armor_count: armor_counts.get(e).cloned(),
+                        spawn_history: spawn_histories.get(e).cloned(),
+                        active_command: active_commands.get(e).cloned(),
+                        hit_info: hit_infos.get(e).cloned(),
+                        clash_cancel: clash_cancels.get(e).cloned(),
+                        hit_shake: hit_shakes.get(e).cloned(),
+                        hitstop: hitstops.get(e).cloned(),
+                        health: healths.get(e).cloned(),
+                        gauge: gauges.get(e).cloned(),
+                        projectile: projectiles.get(e).cloned(),
+                        animation_time: times.get(e).cloned(),
+                        animation_key: Some(key.clone()),
+                    })
+                    .collect::<Vec<_>>();
+                let inputs = buffer.queue().iter().cloned().collect::<Vec<_>>();
+                (clock.frame(), entities, inputs)
+            },
+        );
+
+        let resources = world.exec(
+            |(match_state, clash, trade, random, training, training_dummy, ai, input_history): (
+                Read<MatchState>,
+                Read<ClashSetting>,
+                Read<TradeRule>,
+                Read<GameRandom>,
+                Read<TrainingSetting>,
+                Read<TrainingDummyState>,
+                Read<AiState>,
+                Read<InputHistory>,
+            )| ResourceSnapshot {
+                match_state: match_state.clone(),
+                clash: clash.clone(),
+                trade: trade.clone(),
+                random: random.clone(),
+                training: training.clone(),
+                training_dummy: training_dummy.clone(),
+                ai: ai.clone(),
+                input_history: input_history.clone(),
+            },
+        );
+
+        let mut snapshot = GameSnapshot {
+            frame,
+            entities,
+            resources,
+            inputs,
+            checksum: 0,
+        };
+        snapshot.checksum = snapshot.calc_checksum();
+        snapshot
+    }
+
+    // 保存した状態をワールドに書き戻す
+    // 保存後に生成されたエンティティ(弾など)は削除し，削除されたエンティティは作り直す
+    // 作り直したエンティティには保存したコンポーネントだけが付く
+    pub fn restore(&self, world: &mut World) {
+        let recreated = self.restore_entities(world);
+        let remap = |e: Entity| recreated.get(&e).cloned().unwrap_or(e);
+
+        world.exec(
+            |(
+                mut clock,
+                mut buffer,
+                mut tags,
+                mut transforms,
+                mut directions,
+                mut knockbacks,
+                mut downs,
+                mut jumps,
+                mut damaged,
+                mut skill_counts,
+                mut armor_counts,
+                mut spawn_histories,
+                mut active_commands,
+                mut hit_infos,
+                mut clash_cancels,
+                mut hit_shakes,
+                mut hitstops,
+                mut healths,
+                mut gauges,
+                mut projectiles,
+                mut times,
+                mut keys,
+            ): (
+                Write<FrameClock>,
+                WriteExpect<CircularBuffer<Signal<'static>>>,
+                WriteStorage<PlayerTag>,
+                WriteStorage<Transform>,
+                WriteStorage<Direction>,
+                WriteStorage<Knockback>,
+                WriteStorage<Down>,
+                WriteStorage<Jump>,
+                WriteStorage<Damaged<T>>,
+                WriteStorage<SkillCount<T>>,
+                WriteStorage<ArmorCount<T>>,
+                WriteStorage<SpawnHistory<T>>,
+                WriteStorage<ActiveCommand>,
+                WriteStorage<HitInfo<T>>,
+                WriteStorage<ClashCancel>,
+                WriteStorage<HitShake>,
+                WriteStorage<Hitstop>,
+                WriteStorage<Health>,
+                WriteStorage<Gauge>,
+                WriteStorage<Projectile>,
+                WriteStorage<AnimationTime>,
+                WriteStorage<PlayAnimationKey<T>>,
+            )| {
+                clock.set_frame(self.frame);
+
+                *buffer = CircularBuffer::new(FightInput::BUFFER_SIZE);
+                for input in &self.inputs {
+                    buffer.push(input.clone());
+                }
+
+                for snapshot in &self.entities {
+                    let e = remap(snapshot.entity);
+
+                    // 他のエンティティへの参照は作り直したエンティティに付け替える
+                    let damaged_ids = snapshot.damaged.clone().map(|mut damaged| {
+                        damaged.remap_entities(&remap);
+                        damaged
+                    });
+                    let hit_info = snapshot.hit_info.clone().map(|mut hit_info| {
+                        hit_info.remap_entities(&remap);
+                        hit_info
+                    });
+                    let projectile = snapshot.projectile.clone().map(|mut projectile| {
+                        projectile.remap_entities(&remap);
+                        projectile
+                    });
+
+                    restore_component(&mut tags, e, &snapshot.tag);
+                    restore_component(&mut transforms, e, &snapshot.transform);
+                    restore_component(&mut directions, e, &snapshot.direction);
+                    restore_component(&mut knockbacks, e, &snapshot.knockback);
+                    restore_component(&mut downs, e, &snapshot.down);
+                    restore_component(&mut jumps, e, &snapshot.jump);
+                    restore_component(&mut damaged, e, &damaged_ids);
+                    restore_component(&mut skill_counts, e, &snapshot.skill_count);
+                    restore_component(&mut armor_counts, e, &snapshot.armor_count);
+                    restore_component(&mut spawn_histories, e, &snapshot.spawn_history);
+                    restore_component(&mut active_commands, e, &snapshot.active_command);
+                    restore_component(&mut hit_infos, e, &hit_info);
+                    restore_component(&mut clash_cancels, e, &snapshot.clash_cancel);
+                    restore_component(&mut hit_shakes, e, &snapshot.hit_shake);
+                    restore_component(&mut hitstops, e, &snapshot.hitstop);
+                    restore_component(&mut healths, e, &snapshot.health);
+                    restore_component(&mut gauges, e, &snapshot.gauge);
+                    restore_component(&mut projectiles, e, &projectile);
+                    restore_component(&mut times, e, &snapshot.animation_time);
+                    restore_component(&mut keys, e, &snapshot.animation_key);
+                }
+            },
+        );
+
+        world.exec(
+            |(
+                mut match_state,
+                mut clash,
+                mut trade,
+                mut random,
+                mut training,
+                mut training_dummy,
+                mut ai,
+                mut input_history,
+            ): (
+                Write<MatchState>,
+                Write<ClashSetting>,
+                Write<TradeRule>,
+                Write<GameRandom>,
+                Write<TrainingSetting>,
+                Write<TrainingDummyState>,
+                Write<AiState>,
+                Write<InputHistory>,
+            )| {
+                *match_state = self.resources.match_state.clone();
+                *clash = self.resources.clash.clone();
+                *trade = self.resources.trade.clone();
+                *random = self.resources.random.clone();
+                *training = self.resources.training.clone();
+                *training_dummy = self.resources.training_dummy.clone();
+                *ai = self.resources.ai.clone();
+                // 再シミュレーションで同じフレームの入力を二重に数えない
+                *input_history = self.resources.input_history.clone();
+            },
+        );
+
+        // CPUの入力は次のフレームで読まれるので保存時点の入力を詰めなおす
+        world.exec(|(ai, queues): (Read<AiState>, Read<AiInputQueues>)| {
+            queues.requeue(&ai);
+        });
+    }
+
+    // 保存後に生成されたエンティティを削除し，削除されたエンティティを作り直す
+    // 作り直したエンティティの対応を返す
+    fn restore_entities(&self, world: &mut World) -> BTreeMap<Entity, Entity> {
+        let saved = self
+            .entities
+            .iter()
+            .map(|snapshot| snapshot.entity)
+            .collect::<BTreeSet<_>>();
+
+        let remap = world.exec(
+            |(entities, keys): (Entities, ReadStorage<PlayAnimationKey<T>>)| {
+                for (e, _) in (&*entities, &keys).join() {
+                    if saved.contains(&e) == false {
+                        log::debug!("restore: delete {:?}", e);
+                        if let Err(err) = entities.delete(e) {
+                            log::error!("restore delete error: {:?}", err);
+                        }
+                    }
+                }
+
+                saved
+                    .iter()
+                    .filter(|e| entities.is_alive(**e) == false)
+                    .map(|&e| {
+                        let created = entities.create();
+                        log::debug!("restore: recreate {:?} => {:?}", e, created);
+                        (e, created)
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            },
+        );
+        // 削除を反映する
+        world.maintain();
+        remap
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    // 同期確認用のチェックサム
+    pub fn checksum(&self) -> u64 {
+        self.checksum
+    }
+
+    // エンティティのIDは作り直すと変わるので，同期確認用のキーを作る
+    // プレイヤーはタグ，弾は生成元と生成情報，それ以外はID
+    fn sync_key(&self, e: Entity) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let mut current = e;
+        for _ in 0..MAX_SPAWN_DEPTH {
+            let snapshot = match self.entities.iter().find(|s| s.entity == current) {
+                Some(snapshot) => snapshot,
+                None => break,
+            };
+            if let Some(tag) = snapshot.tag {
+                tag.hash(&mut hasher);
+                return hasher.finish();
+            }
+            match &snapshot.projectile {
+                Some(projectile) => {
+                    projectile.spawned().hash(&mut hasher);
+                    current = projectile.spawner();
+                }
+                None => break,
+            }
+        }
+        current.id().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn calc_checksum(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.frame.hash(&mut hasher);
+
+        let mut entities = self
+            .entities
+            .iter()
+            .map(|snapshot| (self.sync_key(snapshot.entity), snapshot))
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|(key, _)| *key);
+
+        for (key, snapshot) in entities {
+            key.hash(&mut hasher);
+            self.hash_entity(snapshot, &mut hasher);
+        }
+
+        let resources = &self.resources;
+        let state = &resources.match_state;
+        hash_debug(&state.phase, &mut hasher);
+        state.round.hash(&mut hasher);
+        state.phase_frame.hash(&mut hasher);
+        state.rest_frame.hash(&mut hasher);
+        state.wins.hash(&mut hasher);
+        hash_debug(&state.results, &mut hasher);
+        state.reset_requested.hash(&mut hasher);
+        hash_debug(&resources.clash, &mut hasher);
+        hash_debug(&resources.trade, &mut hasher);
+        for (stream, state) in resources.random.streams() {
+            stream.hash(&mut hasher);
+            state.hash(&mut hasher);
+        }
+        hash_debug(&resources.training, &mut hasher);
+        hash_debug(&resources.training_dummy, &mut hasher);
+        hash_debug(&resources.ai, &mut hasher);
+
+        for input in &self.inputs {
+            for (tag, signal) in input {
+                tag.hash(&mut hasher);
+                signal.is_down_flag().bits().hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    // 保存したコンポーネントをすべてハッシュする
+    // エンティティへの参照は同期確認用のキーに置き換える
+    fn hash_entity<H>(&self, snapshot: &EntitySnapshot<T>, hasher: &mut H)
+    where
+        H: Hasher,
+    {
+        snapshot.tag.hash(hasher);
+        if let Some(transform) = &snapshot.transform {
+            let translation = transform.translation();
+            let scale = transform.scale();
+            for value in &[
+                translation.x,
+                translation.y,
+                translation.z,
+                scale.x,
+                scale.y,
+                scale.z,
+            ] {
+                value.to_bits().hash(hasher);
+            }
+        }
+        if let Some(direction) = &snapshot.direction {
+            match direction {
+                Direction::Right => 0u8.hash(hasher),
+                Direction::Left => 1u8.hash(hasher),
+            }
+        }
+        if let Some(knockback) = &snapshot.knockback {
+            knockback.knockback_frame().hash(hasher);
+            knockback.is_blockstun().hash(hasher);
+            knockback.push().to_bits().hash(hasher);
+        }
+        hash_debug(&snapshot.down, hasher);
+        if let Some(jump) = &snapshot.jump {
+            let (vx, vy) = jump.velocity();
+            (vx.to_bits(), vy.to_bits(), jump.is_landed()).hash(hasher);
+        }
+        if let Some(damaged) = &snapshot.damaged {
+            self.hash_ids(damaged.damaged_ids(), hasher);
+        }
+        if let Some(skill_count) = &snapshot.skill_count {
+            for (key, count) in skill_count.counts() {
+                hash_debug(key, hasher);
+                count.hash(hasher);
+            }
+        }
+        hash_debug(&snapshot.armor_count, hasher);
+        hash_debug(&snapshot.spawn_history, hasher);
+        if let Some(active_command) = &snapshot.active_command {
+            let commands = active_command.active_commands().collect::<BTreeSet<_>>();
+            commands.hash(hasher);
+        }
+        if let Some(hit_info) = &snapshot.hit_info {
+            let mut owners = hit_info
+                .damaged_owners
+                .iter()
+                .map(|e| self.sync_key(*e))
+                .collect::<Vec<_>>();
+            owners.sort();
+            owners.hash(hasher);
+            hit_info.attack_owner.map(|e| self.sync_key(e)).hash(hasher);
+            hit_info
+                .attack_entity
+                .map(|e| self.sync_key(e))
+                .hash(hasher);
+            self.hash_ids(hit_info.attack_collision_id.iter(), hasher);
+            self.hash_ids(hit_info.damage_collision_ids.iter(), hasher);
+            hit_info.hitstop.hash(hasher);
+            hit_info.knockback.hash(hasher);
+            hit_info.damage.map(f32::to_bits).hash(hasher);
+            hash_debug(&hit_info.hit_level, hasher);
+            hash_debug(&hit_info.blow, hasher);
+            hit_info.pushback.map(f32::to_bits).hash(hasher);
+            hit_info.armored.hash(hasher);
+            hit_info.durability_damage.hash(hasher);
+            hit_info.reflected_by.map(|e| self.sync_key(e)).hash(hasher);
+            hit_info.absorbed.hash(hasher);
+            hash_debug(&hit_info.clash, hasher);
+            hit_info.clash_owner.map(|e| self.sync_key(e)).hash(hasher);
+            hit_info.trade.hash(hasher);
+            hit_info.counter_hit.hash(hasher);
+            hit_info.blocked.hash(hasher);
+            hit_info
+                .contact_point
+                .map(|(x, y)| (x.to_bits(), y.to_bits()))
+                .hash(hasher);
+            hit_info.shake.hash(hasher);
+        }
+        hash_debug(&snapshot.clash_cancel, hasher);
+        hash_debug(&snapshot.hit_shake, hasher);
+        hash_debug(&snapshot.hitstop, hasher);
+        hash_debug(&snapshot.health, hasher);
+        hash_debug(&snapshot.gauge, hasher);
+        if let Some(projectile) = &snapshot.projectile {
+            self.sync_key(projectile.owner()).hash(hasher);
+            self.sync_key(projectile.spawner()).hash(hasher);
+            projectile.spawned().hash(hasher);
+            let (x, y) = projectile.velocity();
+            x.to_bits().hash(hasher);
+            y.to_bits().hash(hasher);
+            projectile.rest_frame().hash(hasher);
+            projectile.rest_hit().hash(hasher);
+            projectile.durability().hash(hasher);
+        }
+        if let Some(time) = &snapshot.animation_time {
+            time.play_time().to_bits().hash(hasher);
+        }
+        if let Some((file, pack, anim)) = snapshot
+            .animation_key
+            .as_ref()
+            .and_then(|key| key.play_key())
+        {
+            hash_debug(&(file, pack, anim), hasher);
+        }
+    }
+
+    // 判定IDの集合は持ち主のキー順に並べてハッシュする
+    fn hash_ids<'i, I, H>(&self, ids: I, hasher: &mut H)
+    where
+        T: 'i,
+        I: Iterator<Item = &'i DamageCollisionId<T>>,
+        H: Hasher,
+    {
+        let mut ids = ids
+            .map(|id| (self.sync_key(id.collision_owner()), format!("{:?}", id)))
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.hash(hasher);
+    }
+}
+
+// エンティティへの参照を含まない値は表示形式でハッシュする
+fn hash_debug<D, H>(value: &D, hasher: &mut H)
+where
+    D: Debug,
+    H: Hasher,
+{
+    format!("{:?}", value).hash(hasher);
+}
+
+fn restore_component<C>(storage: &mut WriteStorage<C>, e: Entity, component: &Option<C>)
+where
+    C: Component + Clone,
+{
+    match component {
+        Some(component) => {
+            if let Err(err) = storage.insert(e, component.clone()) {
+                log::error!("restore error: {:?}", err);
+            }
+        }
+        None => {
+            storage.remove(e);
+        }
+    }
+}
+
+// 直近数フレーム分のスナップショットを保持する
+pub struct SnapshotBuffer<T>
+where
+    T: AnimationFile,
+{
+    snapshots: VecDeque<GameSnapshot<T>>,
+    capacity: usize,
+}
+
+impl<T> SnapshotBuffer<T>
+where
+    T: AnimationFile,
+{
+    pub fn new(capacity: usize) -> Self {
+        SnapshotBuffer {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    // 同じフレームのスナップショットは上書き
+    pub fn save(&mut self, snapshot: GameSnapshot<T>) {
+        self.discard_after(snapshot.frame());
+        if self.snapshots.len() >= self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    pub fn get(&self, frame: u64) -> Option<&GameSnapshot<T>> {
+        self.snapshots.iter().find(|s| s.frame() == frame)
+    }
+
+    pub fn checksum(&self, frame: u64) -> Option<u64> {
+        self.get(frame).map(|s| s.checksum())
+    }
+
+    pub fn oldest_frame(&self) -> Option<u64> {
+        self.snapshots.front().map(|s| s.frame())
+    }
+
+    // 指定フレーム以降を破棄(巻き戻し後の再シミュレーション用)
+    pub fn discard_after(&mut self, frame: u64) {
+        while self
+            .snapshots
+            .back()
+            .map(|s| s.frame() >= frame)
+            .unwrap_or(false)
+        {
+            self.snapshots.pop_back();
+        }
+    }
+}
@@ -1,10 +1,23 @@
+pub(crate) mod ai;
 pub(crate) mod apply_hit_info;
+pub(crate) mod clash;
 pub(crate) mod command_activate;
 pub(crate) mod damage_judge;
 #[cfg(feature = "debug")]
 pub(crate) mod debug;
 pub(crate) mod direction;
+pub(crate) mod down;
 pub(crate) mod extrude;
+pub(crate) mod hit_shake;
+pub(crate) mod hitstop;
+pub(crate) mod input;
+pub(crate) mod input_history;
 pub(crate) mod knockback;
+pub(crate) mod match_flow;
+pub(crate) mod movement;
+pub(crate) mod projectile;
 pub(crate) mod register_collider;
 pub(crate) mod skill_count;
+pub(crate) mod trade;
+pub(crate) mod training;
+pub(crate) mod training_hud;
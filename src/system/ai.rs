@@ -0,0 +1,305 @@
+use crate::{
+    components::{Direction, Knockback, PlayerTag},
+    input::{InputFlag, QueueSource},
+    paramater::AnimationParam,
+    resource::{
+        ai::{AiController, AiInputQueues, AiSetting, AiState, AttackPhase, Observation},
+        clock::FrameClock,
+        input_source::{InputSources, SourceOwner},
+        random::{GameRandom, RandomStream},
+    },
+    types::{analyze::SkillInfomation, random::XorShift},
+};
+use amethyst::ecs::{Join, Read, ReadStorage, System, Write};
+use amethyst_sprite_studio::{components::BuildRequireData, traits::animation_file::AnimationFile};
+use std::collections::BTreeMap;
+
+// ガード，移動を続けるフレーム数
+const GUARD_FRAME: usize = 10;
+const WALK_FRAME: usize = 8;
+// 攻撃が届く距離でなにもしていない相手に技を出す確率
+const POKE_RATE: f32 = 0.3;
+
+const BUTTONS: [InputFlag; 4] = [InputFlag::A, InputFlag::B, InputFlag::C, InputFlag::D];
+
+// 入力ミス時に代わりに入る入力
+const MISTAKES: [InputFlag; 6] = [
+    InputFlag::UP,
+    InputFlag::DOWN,
+    InputFlag::RIGHT,
+    InputFlag::LEFT,
+    InputFlag::A,
+    InputFlag::B,
+];
+
+impl AiController {
+    // 反応遅延分前の状況をもとに入力を決める
+    fn next_input(&mut self, rng: &mut XorShift, observation: Observation) -> InputFlag {
+        let delay = self.config.difficulty.reaction_delay();
+        self.observations.push_back(observation);
+        while self.observations.len() > delay + 1 {
+            self.observations.pop_front();
+        }
+
+        if self.plan.is_empty() == true && self.observations.len() > delay {
+            if let Some(&observed) = self.observations.front() {
+                self.think(rng, observed);
+                if self.plan.is_empty() == false {
+                    self.mistake(rng);
+                }
+            }
+        }
+
+        self.plan.pop_front().unwrap_or(InputFlag::empty())
+    }
+
+    // 入力ミス
+    // 行動を決めるごとに1回判定し，決めた行動を別の入力に置き換える
+    fn mistake(&mut self, rng: &mut XorShift) {
+        if rng.chance(self.config.difficulty.error_rate()) == true {
+            let input = MISTAKES[rng.next_range(MISTAKES.len() as u64) as usize];
+            let frames = self.plan.len();
+            self.plan = std::iter::repeat(input).take(frames).collect();
+        }
+    }
+
+    fn think(&mut self, rng: &mut XorShift, observed: Observation) {
+        let config = self.config;
+
+        if observed.self_knockback == true {
+            // やられ中は何もしない
+            return;
+        }
+
+        let threatened = match observed.opponent_phase {
+            AttackPhase::Startup | AttackPhase::Active => true,
+            _ => false,
+        };
+        let punishable =
+            observed.opponent_knockback == true || observed.opponent_phase == AttackPhase::Recovery;
+
+        if threatened == true && observed.distance <= config.approach_range {
+            if rng.chance(config.guard_rate) == true {
+                let guard = if rng.chance(0.5) == true {
+                    InputFlag::LEFT
+                } else {
+                    InputFlag::LEFT_DOWN
+                };
+                self.hold(guard, GUARD_FRAME);
+            }
+        } else if punishable == true && observed.distance <= config.attack_range {
+            self.attack(rng);
+        } else if observed.distance > config.approach_range {
+            self.hold(InputFlag::RIGHT, WALK_FRAME);
+        } else if observed.distance <= config.attack_range {
+            if rng.chance(POKE_RATE) == true {
+                self.attack(rng);
+            }
+        } else if rng.chance(0.5) == true {
+            self.hold(InputFlag::RIGHT, WALK_FRAME);
+        } else {
+            self.hold(InputFlag::LEFT, WALK_FRAME);
+        }
+    }
+
+    fn hold(&mut self, input: InputFlag, frames: usize) {
+        self.plan.extend(std::iter::repeat(input).take(frames));
+    }
+
+    fn attack(&mut self, rng: &mut XorShift) {
+        let button = BUTTONS[rng.next_range(BUTTONS.len() as u64) as usize];
+        // 押しっぱなしにならないように一度離す
+        self.plan.push_back(button);
+        self.plan.push_back(InputFlag::empty());
+    }
+}
+
+// CPUの入力を生成する
+// 生成した入力は次のフレームで反映される
+// CPUの状態は AiState に置く
+pub struct AiSystem<T>
+where
+    T: AnimationFile,
+{
+    skills: BTreeMap<(T::FileId, T::PackKey, T::AnimationKey), SkillInfomation>,
+}
+
+impl<T> AiSystem<T>
+where
+    T: AnimationFile,
+{
+    pub fn new() -> Self {
+        AiSystem {
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'s, T> System<'s> for AiSystem<T>
+where
+    T: AnimationFile<UserData = AnimationParam>,
+{
+    type SystemData = (
+        Read<'s, AiSetting>,
+        Write<'s, AiState>,
+        Write<'s, AiInputQueues>,
+        Write<'s, InputSources>,
+        Write<'s, GameRandom>,
+        Read<'s, FrameClock>,
+        ReadStorage<'s, PlayerTag>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, Knockback>,
+        BuildRequireData<'s, T>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            setting,
+            mut state,
+            mut queues,
+            mut sources,
+            mut random,
+            clock,
+            tags,
+            directions,
+            knockbacks,
+            (play_time, key, transforms, _, storage, store),
+        ): Self::SystemData,
+    ) {
+        // 設定から外れたプレイヤーはデバイス入力に戻す
+        let removed = queues
+            .queues
+            .keys()
+            .filter(|tag| setting.config(**tag).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        for tag in removed {
+            queues.queues.remove(&tag);
+            sources.release(tag, SourceOwner::Ai);
+        }
+        state
+            .controllers
+            .retain(|tag, _| setting.config(*tag).is_some());
+
+        for (&tag, &config) in setting.controllers() {
+            if queues.queues.contains_key(&tag) == false {
+                let (source, queue) = QueueSource::new();
+                if sources.claim(tag, SourceOwner::Ai, source) == false {
+                    // トレーニングダミーなど他のシステムが操作している間は動かさない
+                    log::debug!("ai player is used: {:?} {:?}", tag, sources.owner(tag));
+                    state.controllers.remove(&tag);
+                    continue;
+                }
+                queues.queues.insert(tag, queue);
+            }
+            // 設定が変わったら作り直す
+            let changed = state
+                .controllers
+                .get(&tag)
+                .map(|controller| controller.config != config)
+                .unwrap_or(true);
+            if changed == true {
+                random.reset(RandomStream::Ai(tag), config.seed);
+                state.controllers.insert(tag, AiController::new(config));
+            }
+        }
+
+        if state.controllers.is_empty() == true {
+            return;
+        }
+
+        // 各プレイヤーの状況
+        let fps = clock.fps() as f32;
+        let mut players = BTreeMap::new();
+        for (&tag, transform, direction, knockback, time, key) in (
+            &tags,
+            &transforms,
+            directions.maybe(),
+            knockbacks.maybe(),
+            play_time.maybe(),
+            key.maybe(),
+        )
+            .join()
+        {
+            let phase = match (time, key.and_then(|key| key.play_key())) {
+                (Some(time), Some((file, pack, anim))) => {
+                    let skill = (*file, *pack, *anim);
+                    // 読み込みが終わっていないアニメーションは次のフレームで取り直す
+                    if self.skills.contains_key(&skill) == false {
+                        if let Some(info) = store
+                            .get_animation_handle(file)
+                            .and_then(|handle| storage.get(handle))
+                            .and_then(|data| SkillInfomation::make_info(data, *pack, *anim))
+                        {
+                            self.skills.insert(skill, info);
+                        }
+                    }
+                    attack_phase(self.skills.get(&skill), time.play_frame(fps))
+                }
+                _ => AttackPhase::None,
+            };
+            players.insert(
+                tag,
+                (
+                    transform.translation().x,
+                    direction.cloned().unwrap_or(Direction::Right),
+                    knockback.map(|k| k.is_knockback()).unwrap_or(false),
+                    phase,
+                ),
+            );
+        }
+
+        for (tag, controller) in state.controllers.iter_mut() {
+            let queue = match queues.queues.get(tag) {
+                Some(queue) => queue,
+                None => continue,
+            };
+            let (x, direction, self_knockback, _) = match players.get(tag) {
+                Some(&player) => player,
+                None => continue,
+            };
+            let opponent = players.iter().find(|(other, _)| *other != tag);
+            let observation = match opponent {
+                Some((_, &(opponent_x, _, opponent_knockback, opponent_phase))) => Observation {
+                    distance: (opponent_x - x).abs(),
+                    self_knockback,
+                    opponent_knockback,
+                    opponent_phase,
+                },
+                None => continue,
+            };
+
+            // 右向き基準の入力を向きに合わせる
+            let rng = random.get_mut(RandomStream::Ai(*tag));
+            let input = match direction {
+                Direction::Right => controller.next_input(rng, observation),
+                Direction::Left => controller.next_input(rng, observation).mirrored(),
+            };
+
+            // 1フレームに1入力
+            controller.queued = input;
+            queue.clear();
+            queue.push(input);
+        }
+    }
+}
+
+fn attack_phase(info: Option<&SkillInfomation>, frame: usize) -> AttackPhase {
+    let info = match info {
+        Some(info) => info,
+        None => return AttackPhase::None,
+    };
+    match (info.attack_frames().first(), info.attack_frames().last()) {
+        (Some(&(start, _)), Some(&(_, end))) => {
+            if frame < start {
+                AttackPhase::Startup
+            } else if frame <= end {
+                AttackPhase::Active
+            } else {
+                AttackPhase::Recovery
+            }
+        }
+        _ => AttackPhase::None,
+    }
+}
@@ -1,12 +1,20 @@
-use crate::components::{Damaged, HitInfo, Knockback};
-use amethyst::ecs::{Entities, Join, System, WriteStorage};
-use amethyst_sprite_studio::{components::AnimationTime, traits::animation_file::AnimationFile};
+use crate::{
+    components::{
+        ArmorCount, Damaged, Direction, Down, Health, HitInfo, HitShake, Hitstop, Knockback,
+        SkillCount,
+    },
+    resource::hit_event::HitEvent,
+    types::SparkKind,
+};
+use amethyst::{
+    ecs::{Entities, Join, ReadStorage, System, Write, WriteStorage},
+    shrev::EventChannel,
+};
+use amethyst_sprite_studio::{components::PlayAnimationKey, traits::animation_file::AnimationFile};
 use std::marker::PhantomData;
 
-// ヒットストップの計算基準とするfps
-const HIT_STOP_FPS: f32 = 60.;
-
 // ヒット情報を適用する
+// 攻撃が当たったらヒットイベントを発行する
 pub struct ApplyHitInfoSystem<T> {
     _translation: PhantomData<T>,
 }
@@ -24,23 +32,92 @@ where
     T: AnimationFile,
 {
     type SystemData = (
+        Write<'s, EventChannel<HitEvent<T>>>,
         Entities<'s>,
-        WriteStorage<'s, HitInfo<T>>,
-        WriteStorage<'s, AnimationTime>,
+        ReadStorage<'s, HitInfo<T>>,
+        ReadStorage<'s, Direction>,
+        WriteStorage<'s, Hitstop>,
         WriteStorage<'s, Damaged<T>>,
         WriteStorage<'s, Knockback>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Down>,
+        ReadStorage<'s, PlayAnimationKey<T>>,
+        ReadStorage<'s, SkillCount<T>>,
+        WriteStorage<'s, ArmorCount<T>>,
+        WriteStorage<'s, HitShake>,
     );
 
     fn run(
         &mut self,
-        (entities, mut hits, mut times, mut damaged, mut knockback): Self::SystemData,
+        (
+            mut hit_events,
+            entities,
+            hits,
+            directions,
+            mut hitstops,
+            mut damaged,
+            mut knockback,
+            mut healths,
+            mut downs,
+            keys,
+            skill_counts,
+            mut armor_counts,
+            mut shakes,
+        ): Self::SystemData,
     ) {
-        for (e, hit, time) in (&*entities, &mut hits, &mut times).join() {
+        for (e, hit) in (&*entities, &hits).join() {
+            // 攻撃を受けたらイベント発行
+            if let (Some(attacker), Some(damage)) = (hit.attack_owner, hit.damage) {
+                hit_events.single_write(HitEvent {
+                    attacker,
+                    defender: e,
+                    collision_id: hit.attack_collision_id,
+                    hit_level: hit.hit_level,
+                    damage,
+                    blocked: hit.blocked,
+                    armored: hit.armored > 0,
+                    counter_hit: hit.counter_hit,
+                    trade: hit.trade,
+                    clash: None,
+                    position: hit.contact_point,
+                    spark: hit.spark(),
+                });
+            }
+            // 相殺はダメージがなくても発行する
+            if let (Some(other), Some(clash)) = (hit.clash_owner, hit.clash) {
+                hit_events.single_write(HitEvent {
+                    attacker: other,
+                    defender: e,
+                    collision_id: None,
+                    hit_level: None,
+                    damage: 0.,
+                    blocked: false,
+                    armored: false,
+                    counter_hit: false,
+                    trade: false,
+                    clash: Some(clash),
+                    position: hit.contact_point,
+                    spark: Some(SparkKind::Clash),
+                });
+            }
+
             // ヒットストップ適用
-            if let Some(hitstop_time) = hit.hitstop {
-                log::debug!("apply hitstop = {} F", hitstop_time);
-                let hitstop_time = hitstop_time as f32 / HIT_STOP_FPS;
-                time.stop(hitstop_time);
+            if let Some(hitstop_frame) = hit.hitstop {
+                log::debug!("apply hitstop = {} F", hitstop_frame);
+                if let Err(err) = hitstops.insert(e, Hitstop::new(hitstop_frame)) {
+                    log::error!("insert hitstop error: {:?}", err);
+                }
+            }
+
+            // 攻撃を受けた側だけ揺らす
+            if let Some(shake) = hit.shake {
+                // 揺れの途中なら描画のずらしを引き継ぐ
+                let offset = shakes.get(e).map(|shake| shake.offset).unwrap_or(0.);
+                let mut hit_shake = HitShake::new(shake);
+                hit_shake.offset = offset;
+                if let Err(err) = shakes.insert(e, hit_shake) {
+                    log::error!("insert hit shake error: {:?}", err);
+                }
             }
 
             // ダメージ判定追加
@@ -55,22 +132,64 @@ where
                 }
             }
 
+            // 体力減少
+            if let (Some(damage), Some(health)) = (hit.damage, healths.get_mut(e)) {
+                health.damage(damage);
+                log::debug!("apply damage = {} => {}", damage, health.current());
+            }
+
+            // アーマーで受け止めた回数を技ごとに記録
+            if hit.armored > 0 {
+                if let Some((&file, &pack, &anim)) = keys.get(e).and_then(|key| key.play_key()) {
+                    let count = skill_counts
+                        .get(e)
+                        .map(|count| count.skill_count(&(file, pack, anim)))
+                        .unwrap_or(0);
+                    if let Ok(entry) = armor_counts.entry(e) {
+                        entry
+                            .or_insert(ArmorCount::new())
+                            .add((file, pack, anim, count), hit.armored);
+                    }
+                }
+            }
+
             // ノックバック時間適用
-            if let Some(knockback_time) = hit.knockback {
+            if let Some(knockback_frame) = hit.knockback {
                 if let Ok(entry) = knockback.entry(e) {
                     let knockback = entry.or_insert(Knockback::new());
                     log::debug!(
-                        "apply knockback = {} secs => {} F",
-                        knockback.knockback_time(),
-                        knockback_time
+                        "apply knockback = {} F => {} F",
+                        knockback.knockback_frame(),
+                        knockback_frame
                     );
-                    let knockback_time = knockback_time as f32 / HIT_STOP_FPS;
-                    knockback.set_knockback(knockback_time);
+                    if hit.blocked == true {
+                        knockback.set_blockstun(knockback_frame);
+                    } else {
+                        knockback.set_knockback(knockback_frame);
+                    }
+
+                    // 向いている方向の反対に押し返す
+                    if let Some(pushback) = hit.pushback {
+                        let sign = match directions.get(e).cloned().unwrap_or(Direction::Right) {
+                            Direction::Right => -1.,
+                            Direction::Left => 1.,
+                        };
+                        knockback.set_pushback(pushback * sign);
+                    }
                 }
             }
 
-            // ヒット情報のリセット
-            *hit = HitInfo::default();
+            // ダウンする攻撃ならノックバック後にダウン
+            if let Some((knockdown, blow)) = hit
+                .blow
+                .and_then(|blow| blow.knockdown.map(|knockdown| (knockdown, blow)))
+            {
+                log::debug!("apply knockdown = {:?}", knockdown);
+                let down = Down::new(knockdown, blow.ground_bounce, blow.wall_bounce);
+                if let Err(err) = downs.insert(e, down) {
+                    log::error!("insert down error: {:?}", err);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,62 @@
+use crate::{
+    components::{ClashCancel, HitInfo, Hitstop},
+    resource::clash::ClashSetting,
+};
+use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// 攻撃同士の相殺後のキャンセル猶予を管理する
+// ヒット情報の適用後に実行する
+pub struct ClashSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> ClashSystem<T> {
+    pub fn new() -> Self {
+        ClashSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for ClashSystem<T>
+where
+    T: AnimationFile,
+{
+    type SystemData = (
+        Read<'s, ClashSetting>,
+        Entities<'s>,
+        ReadStorage<'s, HitInfo<T>>,
+        ReadStorage<'s, Hitstop>,
+        WriteStorage<'s, ClashCancel>,
+    );
+
+    fn run(&mut self, (setting, entities, hits, hitstops, mut clash_cancels): Self::SystemData) {
+        // ヒットストップ中は猶予を減らさない
+        let mut finished = vec![];
+        for (e, clash_cancel, _) in (&*entities, &mut clash_cancels, !&hitstops).join() {
+            clash_cancel.decrement();
+            if clash_cancel.rest_frame() == 0 {
+                finished.push(e);
+            }
+        }
+        for e in finished {
+            clash_cancels.remove(e);
+        }
+
+        if setting.cancel_frame == 0 || setting.cancel.is_empty() == true {
+            return;
+        }
+
+        // 相殺したらキャンセル猶予を設定
+        for (e, hit) in (&*entities, &hits).join() {
+            if hit.clash().is_some() {
+                let clash_cancel = ClashCancel::new(setting.cancel_frame, setting.cancel);
+                if let Err(err) = clash_cancels.insert(e, clash_cancel) {
+                    log::error!("insert clash cancel error: {:?}", err);
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,11 @@
-use crate::traits::{ExtrudeFilter, HitType, UpdateHitInfo, UpdateHitInfoType};
-use amethyst::ecs::{Entity, ReaderId, System, Write, WriteStorage};
+use crate::{
+    resource::diagnostic::HitDiagnostic,
+    traits::{ExtrudeFilter, HitType, UpdateHitInfo, UpdateHitInfoType},
+};
+use amethyst::{
+    ecs::{Entity, Join, ReaderId, System, Write, WriteStorage},
+    shrev::EventChannel,
+};
 use amethyst_aabb::event::{ContactEvent, ContactEventChannel};
 
 // ダメージ処理をするための情報を統合するシステム
@@ -26,34 +32,60 @@ where
 {
     type SystemData = (
         Write<'s, ContactEventChannel<H::Paramater>>,
+        Write<'s, EventChannel<HitDiagnostic>>,
         WriteStorage<'s, H>,
         <H::Paramater as ExtrudeFilter<'s>>::SystemData,
         H::SystemData,
     );
 
-    fn run(&mut self, (mut channel, mut hits, filter_params, hit_info_params): Self::SystemData) {
+    fn run(
+        &mut self,
+        (mut channel, mut diagnostics, mut hits, filter_params, hit_info_params): Self::SystemData,
+    ) {
         if self.reader.is_none() == true {
             self.reader = channel.register_reader().into();
         }
 
-        for event in channel.read(self.reader.as_mut().unwrap()).filter(
+        // 前フレームのヒット情報をリセット
+        // 適用後もフレーム中は参照できるようにここでリセットする
+        for hit in (&mut hits).join() {
+            *hit = H::default();
+        }
+
+        // 接触の順番によって結果が変わらないようにエンティティ順に処理する
+        let mut events = channel
+            .read(self.reader.as_mut().unwrap())
+            .filter(
+                |ContactEvent {
+                     entity1,
+                     entity2,
+                     args1,
+                     args2,
+                     ..
+                 }| {
+                    // 押出判定を行わないもの限定
+                    <H::Paramater as ExtrudeFilter<'s>>::extrude_filter(
+                        *entity1,
+                        args1,
+                        *entity2,
+                        args2,
+                        &filter_params,
+                    ) == false
+                },
+            )
+            .collect::<Vec<_>>();
+        events.sort_by_key(
             |ContactEvent {
-                 entity1,
-                 entity2,
-                 args1,
-                 args2,
-                 ..
+                 entity1, entity2, ..
              }| {
-                // 押出判定を行わないもの限定
-                <H::Paramater as ExtrudeFilter<'s>>::extrude_filter(
-                    *entity1,
-                    args1,
-                    *entity2,
-                    args2,
-                    &filter_params,
-                ) == false
+                (
+                    std::cmp::min(*entity1, *entity2),
+                    std::cmp::max(*entity1, *entity2),
+                )
             },
-        ) {
+        );
+
+        for event in events {
             let ContactEvent {
                 entity1,
                 entity2,
@@ -62,10 +94,37 @@ where
                 ..
             } = event;
 
-            let (attack, damage, attack_param, damage_param) = match H::check_hit_type(args1, args2)
-            {
+            // 想定外の組み合わせは無視して通知する
+            let hit_type = match H::check_hit_type(args1, args2) {
+                Ok(hit_type) => hit_type,
+                Err(err) => {
+                    log::warn!("{}: {:?} {:?}", err, entity1, entity2);
+                    diagnostics.single_write(HitDiagnostic::UnexpectedHitType {
+                        entity1: *entity1,
+                        entity2: *entity2,
+                        description: err.0,
+                    });
+                    continue;
+                }
+            };
+
+            let (attack, damage, attack_param, damage_param) = match hit_type {
                 HitType::Attack => (*entity1, *entity2, args1, args2),
                 HitType::Damage => (*entity2, *entity1, args2, args1),
+                HitType::Clash => {
+                    if let Err(err) = update_clash(
+                        *entity1,
+                        *entity2,
+                        args1,
+                        args2,
+                        &hit_info_params,
+                        &mut hits,
+                    ) {
+                        log::error!("update clash error: {:?}", err);
+                    }
+                    continue;
+                }
+                HitType::Ignore => continue,
             };
 
             match update_info(
@@ -101,7 +160,7 @@ where
 
     let attack_cancels = {
         let damage_hit_info = hits.entry(damage)?.or_insert(H::default());
-        damage_hit_info.damage_update(damage, attack_param, damage_param, data)
+        damage_hit_info.damage_update(attack, attack_param, damage_param, data)
     };
 
     for (e, cancel) in damage_cancels {
@@ -117,3 +176,39 @@ where
 
     Ok(())
 }
+
+// 相殺など双方が影響を受ける接触の更新
+fn update_clash<'s, H>(
+    entity1: Entity,
+    entity2: Entity,
+    param1: &H::Paramater,
+    param2: &H::Paramater,
+    data: &H::SystemData,
+    hits: &mut WriteStorage<H>,
+) -> amethyst::Result<()>
+where
+    H: UpdateHitInfo<'s>,
+{
+    let cancels1 = {
+        let hit_info = hits.entry(entity1)?.or_insert(H::default());
+        hit_info.clash_update(entity2, param1, param2, data)
+    };
+
+    let cancels2 = {
+        let hit_info = hits.entry(entity2)?.or_insert(H::default());
+        hit_info.clash_update(entity1, param2, param1, data)
+    };
+
+    for (e, cancel) in cancels1 {
+        if let Some(hit) = hits.get_mut(e) {
+            hit.cancel(entity1, cancel);
+        }
+    }
+    for (e, cancel) in cancels2 {
+        if let Some(hit) = hits.get_mut(e) {
+            hit.cancel(entity2, cancel);
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use crate::{
     components::PlayerTag,
     input::{FightInput, InputFlag, InputSignal},
+    resource::input_history::InputHistory,
 };
 use amethyst::{
-    ecs::{Entity, ReadExpect, System, World, WriteStorage},
+    ecs::{Entity, Read, ReadExpect, System, World, WriteStorage},
     ui::{UiFinder, UiText},
     utils::circular_buffer::CircularBuffer,
 };
@@ -14,19 +15,15 @@ pub struct InputDebugSystem {
     debug_ui_stick: BTreeMap<PlayerTag, Entity>,
     debug_ui_button: BTreeMap<PlayerTag, Entity>,
     debug_ui_event: BTreeMap<PlayerTag, Entity>,
-    log_buffer: BTreeMap<PlayerTag, CircularBuffer<InputFlag>>,
     last_key: BTreeMap<PlayerTag, InputSignal>,
 }
 
-const BUFFER_SIZE: usize = 20;
-
 impl InputDebugSystem {
     pub fn new(_: &mut World) -> Self {
         InputDebugSystem {
             debug_ui_stick: Default::default(),
             debug_ui_button: Default::default(),
             debug_ui_event: Default::default(),
-            log_buffer: Default::default(),
             last_key: Default::default(),
         }
     }
@@ -37,10 +34,11 @@ impl<'s> System<'s> for InputDebugSystem {
         UiFinder<'s>,
         WriteStorage<'s, UiText>,
         ReadExpect<'s, CircularBuffer<<FightInput as InputParser<'s>>::InputSignal>>,
+        Read<'s, InputHistory>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (finder, mut texts, input_buffer) = data;
+        let (finder, mut texts, input_buffer, history) = data;
 
         self.update_log(PlayerTag::P1, &input_buffer);
         self.update_log(PlayerTag::P2, &input_buffer);
@@ -48,8 +46,8 @@ impl<'s> System<'s> for InputDebugSystem {
         self.find_ui(&finder, PlayerTag::P1);
         self.find_ui(&finder, PlayerTag::P2);
 
-        self.update_ui(PlayerTag::P1, &mut texts);
-        self.update_ui(PlayerTag::P2, &mut texts);
+        self.update_ui(PlayerTag::P1, &mut texts, &history);
+        self.update_ui(PlayerTag::P2, &mut texts, &history);
     }
 }
 
@@ -84,30 +82,6 @@ impl InputDebugSystem {
             .last()
             .and_then(|f| f.get(&tag))?;
 
-        // 実際の履歴の最後と履歴の最後が違ったら差分を記録
-        let logged = match self.last_key.get(&tag) {
-            Some(log_last) => {
-                let diff = input_last.is_push_flag() ^ log_last.is_push_flag();
-                if diff.is_empty() {
-                    None
-                } else {
-                    Some(input_last)
-                }
-            }
-            _ => None,
-        };
-
-        let entry = self
-            .log_buffer
-            .entry(tag)
-            .or_insert(CircularBuffer::new(BUFFER_SIZE));
-
-        if let Some(logged) = logged {
-            if logged.is_push_flag().is_empty() == false {
-                entry.push(logged.is_push_flag());
-            }
-        }
-
         self.last_key.insert(tag, *input_last);
 
         Some(())
@@ -117,12 +91,13 @@ impl InputDebugSystem {
         &mut self,
         tag: PlayerTag,
         texts: &mut WriteStorage<'s, UiText>,
+        history: &InputHistory,
     ) -> Option<()> {
-        update_event(
-            self.debug_ui_event.get(&tag),
-            texts,
-            self.log_buffer.get(&tag)?, // 上で絶対入れるので[]アクセスでOK
-        );
+        if self.last_key.contains_key(&tag) == false {
+            return None;
+        }
+
+        update_event(self.debug_ui_event.get(&tag), texts, history, tag);
 
         update_stick_ui(
             self.debug_ui_stick.get(&tag),
@@ -202,12 +177,14 @@ fn update_stick_button(
 fn update_event(
     debug_ui_button: Option<&Entity>,
     texts: &mut WriteStorage<UiText>,
-    buffer: &CircularBuffer<InputFlag>,
+    history: &InputHistory,
+    tag: PlayerTag,
 ) -> Option<()> {
     let ui = debug_ui_button?;
+    // 新しい入力を上に表示
     let mut output = String::new();
-    for f in buffer.queue().iter() {
-        output.push_str(&format!("{}\n", f));
+    for entry in history.latest_entries(tag) {
+        output.push_str(&format!("{:>4} {}\n", entry.frames(), entry.notation()));
     }
 
     let text_ui = texts.get_mut(*ui)?;
@@ -0,0 +1,175 @@
+use crate::{
+    components::{Direction, Down, DownState, Hitstop, Knockback, PlayerTag, TechRoll},
+    input::{FightInput, InputFlag, InputSignal},
+    paramater::Knockdown,
+    resource::{knockdown::KnockdownRule, stage::Stage},
+};
+use amethyst::{
+    core::Transform,
+    ecs::{Entities, Join, Read, ReadExpect, ReadStorage, System, WriteStorage},
+    utils::circular_buffer::CircularBuffer,
+};
+use input_handle::traits::InputParser;
+
+type Signal<'a> = <FightInput as InputParser<'a>>::InputSignal;
+
+// ダウンから起き上がりまでの状態遷移を行う
+// ノックバック更新後に実行する
+pub struct DownSystem;
+
+impl DownSystem {
+    pub fn new() -> Self {
+        DownSystem
+    }
+}
+
+impl<'s> System<'s> for DownSystem {
+    type SystemData = (
+        Read<'s, KnockdownRule>,
+        Read<'s, Stage>,
+        ReadExpect<'s, CircularBuffer<Signal<'s>>>,
+        Entities<'s>,
+        ReadStorage<'s, PlayerTag>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Hitstop>,
+        WriteStorage<'s, Knockback>,
+        WriteStorage<'s, Down>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            rule,
+            stage,
+            buffer,
+            entities,
+            tags,
+            directions,
+            transforms,
+            hitstops,
+            mut knockbacks,
+            mut downs,
+        ): Self::SystemData,
+    ) {
+        let mut wake_up = vec![];
+        // ヒットストップ中は進めない
+        for (e, down, _, tag, direction, transform, knockback) in (
+            &*entities,
+            &mut downs,
+            !&hitstops,
+            tags.maybe(),
+            directions.maybe(),
+            transforms.maybe(),
+            (&mut knockbacks).maybe(),
+        )
+            .join()
+        {
+            match down.state() {
+                DownState::Falling => {
+                    let knockback = match knockback {
+                        Some(knockback) => knockback,
+                        None => {
+                            down.set_state(DownState::Lying { frame: 0 });
+                            continue;
+                        }
+                    };
+
+                    if knockback.is_knockback() == true {
+                        let x = transform.map(|t| t.translation().x);
+                        if down.wall_bounce == true && x.map(|x| stage.is_wall(x)).unwrap_or(false)
+                        {
+                            log::debug!("wall bounce: {:?}", e);
+                            down.wall_bounce = false;
+                            knockback.set_knockback(rule.bounce_frame);
+                        }
+                    } else if down.ground_bounce == true {
+                        log::debug!("ground bounce: {:?}", e);
+                        down.ground_bounce = false;
+                        knockback.set_knockback(rule.bounce_frame);
+                    } else {
+                        log::debug!("knockdown: {:?} {:?}", e, down.knockdown());
+                        down.set_state(DownState::Lying { frame: 0 });
+                    }
+                }
+                DownState::Lying { frame: lying } => {
+                    let tech = match (down.knockdown(), tag) {
+                        (Knockdown::Soft, Some(tag)) if lying < rule.tech_window => tech_input(
+                            &buffer,
+                            *tag,
+                            direction.cloned().unwrap_or(Direction::Right),
+                            rule.tech_input_frame,
+                        ),
+                        _ => None,
+                    };
+
+                    if let Some(direction) = tech {
+                        log::debug!("tech roll: {:?} {:?}", e, direction);
+                        down.set_state(DownState::TechRoll {
+                            direction,
+                            rest: rule.tech_roll_frame,
+                        });
+                    } else if lying + 1 >= rule.down_frame(down.knockdown()) {
+                        down.set_state(DownState::WakeUp {
+                            rest: rule.wake_up_frame,
+                        });
+                    } else {
+                        down.set_state(DownState::Lying { frame: lying + 1 });
+                    }
+                }
+                DownState::TechRoll { direction, rest } => {
+                    let rest = rest.saturating_sub(1);
+                    if rest == 0 {
+                        wake_up.push(e);
+                    } else {
+                        down.set_state(DownState::TechRoll { direction, rest });
+                    }
+                }
+                DownState::WakeUp { rest } => {
+                    let rest = rest.saturating_sub(1);
+                    if rest == 0 {
+                        wake_up.push(e);
+                    } else {
+                        down.set_state(DownState::WakeUp { rest });
+                    }
+                }
+            }
+        }
+
+        // 起き上がったのでダウン状態を解除
+        for e in wake_up {
+            log::debug!("wake up: {:?}", e);
+            downs.remove(e);
+        }
+    }
+}
+
+// 直近の入力からボタンを押していたら受け身
+// 方向は入力時のレバー(向き基準)で決める
+fn tech_input(
+    buffer: &CircularBuffer<Signal<'_>>,
+    tag: PlayerTag,
+    direction: Direction,
+    input_frame: usize,
+) -> Option<TechRoll> {
+    let buttons = InputFlag::A | InputFlag::B | InputFlag::C | InputFlag::D;
+    let signal: &InputSignal = buffer
+        .queue()
+        .iter()
+        .rev()
+        .take(input_frame.max(1))
+        .filter_map(|signal| signal.get(&tag))
+        .find(|signal| signal.is_push_flag().intersects(buttons))?;
+
+    let lever = match direction {
+        Direction::Right => signal.is_down_flag(),
+        Direction::Left => signal.is_down_flag().mirrored(),
+    };
+    if lever.intersects(InputFlag::RIGHT | InputFlag::RIGHT_DOWN | InputFlag::RIGHT_UP) {
+        Some(TechRoll::Forward)
+    } else if lever.intersects(InputFlag::LEFT | InputFlag::LEFT_DOWN | InputFlag::LEFT_UP) {
+        Some(TechRoll::Back)
+    } else {
+        Some(TechRoll::Neutral)
+    }
+}
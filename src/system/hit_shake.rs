@@ -0,0 +1,34 @@
+use crate::components::HitShake;
+use amethyst::ecs::{Entities, Join, System, WriteStorage};
+
+// 揺れの最大幅
+const SHAKE_AMPLITUDE: f32 = 3.;
+
+// ヒットストップ中の揺れのずらしを更新する
+// 座標は変えないので，描画側で HitShake::offset を加える
+// ヒット情報の適用後に実行する
+pub struct HitShakeSystem;
+
+impl HitShakeSystem {
+    pub fn new() -> Self {
+        HitShakeSystem
+    }
+}
+
+impl<'s> System<'s> for HitShakeSystem {
+    type SystemData = (Entities<'s>, WriteStorage<'s, HitShake>);
+
+    fn run(&mut self, (entities, mut shakes): Self::SystemData) {
+        let mut finished = vec![];
+        for (e, shake) in (&*entities, &mut shakes).join() {
+            shake.offset = shake.next_offset(SHAKE_AMPLITUDE);
+            shake.decrement();
+            if shake.rest_frame() == 0 {
+                finished.push(e);
+            }
+        }
+        for e in finished {
+            shakes.remove(e);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{components::Hitstop, resource::clock::FrameClock};
+use amethyst::ecs::{Entities, Join, Read, System, WriteStorage};
+use amethyst_sprite_studio::components::AnimationTime;
+
+// ヒットストップをフレーム数で進める
+// 止まっているフレームはアニメーションを1フレーム分だけ止める
+// アニメーションの更新より前に実行する
+pub struct HitstopSystem;
+
+impl HitstopSystem {
+    pub fn new() -> Self {
+        HitstopSystem
+    }
+}
+
+impl<'s> System<'s> for HitstopSystem {
+    type SystemData = (
+        Read<'s, FrameClock>,
+        Entities<'s>,
+        WriteStorage<'s, Hitstop>,
+        WriteStorage<'s, AnimationTime>,
+    );
+
+    fn run(&mut self, (clock, entities, mut hitstops, mut times): Self::SystemData) {
+        let mut finished = vec![];
+        for (e, hitstop, time) in (&*entities, &mut hitstops, (&mut times).maybe()).join() {
+            // 前のフレームで止まり終わった
+            if hitstop.rest_frame() == 0 {
+                finished.push(e);
+                continue;
+            }
+
+            if let Some(time) = time {
+                time.stop(clock.frame_seconds());
+            }
+            hitstop.decrement();
+        }
+        for e in finished {
+            hitstops.remove(e);
+        }
+    }
+}
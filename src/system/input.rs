@@ -0,0 +1,79 @@
+use crate::{
+    binding::FightBindings,
+    input::{make_signal, FightInput, InputFlag},
+    resource::{input_source::InputSources, match_state::MatchState, replay::InputReplay},
+};
+use amethyst::{
+    ecs::{Read, System, Write, WriteExpect},
+    input::InputHandler,
+    shrev::EventChannel,
+    utils::circular_buffer::CircularBuffer,
+};
+use input_handle::traits::InputParser;
+
+type Signal<'a> = <FightInput as InputParser<'a>>::InputSignal;
+type Event<'a> = <FightInput as InputParser<'a>>::Event;
+
+// 各プレイヤーの入力元から入力をバッファに詰めてコマンドイベントを生成する
+// リプレイ再生中は入力元の代わりに記録した入力を使う
+pub struct FightInputSystem;
+
+impl FightInputSystem {
+    pub fn new() -> Self {
+        FightInputSystem
+    }
+}
+
+impl<'s> System<'s> for FightInputSystem {
+    type SystemData = (
+        Read<'s, InputHandler<FightBindings>>,
+        WriteExpect<'s, CircularBuffer<Signal<'s>>>,
+        Write<'s, EventChannel<Event<'s>>>,
+        Write<'s, InputReplay>,
+        Write<'s, InputSources>,
+        Read<'s, MatchState>,
+        <FightInput as InputParser<'s>>::SystemData,
+    );
+
+    fn run(
+        &mut self,
+        (
+            handler,
+            mut buffer,
+            mut channel,
+            mut replay,
+            mut sources,
+            match_state,
+            parse_data,
+        ): Self::SystemData,
+    ) {
+        #[cfg(feature = "profiler")]
+        thread_profiler::profile_scope!("fight_input");
+
+        let signal = match replay.next_frame() {
+            Some(signal) => signal,
+            None => {
+                let prev = buffer.queue().back();
+                sources
+                    .sources_mut()
+                    .map(|(&tag, source)| {
+                        let input = source.next_input(tag, &handler);
+                        // ラウンド開始，終了演出中は入力なし
+                        let input = if match_state.is_input_locked() == true {
+                            InputFlag::empty()
+                        } else {
+                            input
+                        };
+                        (tag, make_signal(input, prev.and_then(|p| p.get(&tag))))
+                    })
+                    .collect()
+            }
+        };
+
+        replay.record(&signal);
+        buffer.push(signal);
+
+        let events = <FightInput as InputParser<'s>>::parse_input(&buffer, parse_data);
+        channel.iter_write(events);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{
+    components::{Direction, PlayerTag},
+    input::FightInput,
+    resource::input_history::InputHistory,
+};
+use amethyst::{
+    ecs::{Join, ReadExpect, ReadStorage, System, Write},
+    utils::circular_buffer::CircularBuffer,
+};
+use input_handle::traits::InputParser;
+
+type Signal<'a> = <FightInput as InputParser<'a>>::InputSignal;
+
+// 入力バッファの最新の入力を履歴に追加する
+// 表記を向き基準にするため入力時の向きも記録する
+pub struct InputHistorySystem;
+
+impl InputHistorySystem {
+    pub fn new() -> Self {
+        InputHistorySystem
+    }
+}
+
+impl<'s> System<'s> for InputHistorySystem {
+    type SystemData = (
+        ReadExpect<'s, CircularBuffer<Signal<'s>>>,
+        Write<'s, InputHistory>,
+        ReadStorage<'s, PlayerTag>,
+        ReadStorage<'s, Direction>,
+    );
+
+    fn run(&mut self, (buffer, mut history, tags, directions): Self::SystemData) {
+        if let Some(signal) = buffer.queue().iter().last() {
+            for (tag, input) in signal {
+                let direction = (&tags, &directions)
+                    .join()
+                    .find(|(t, _)| *t == tag)
+                    .map(|(_, direction)| *direction)
+                    .unwrap_or(Direction::Right);
+                history.push(*tag, input.is_down_flag(), direction);
+            }
+        }
+    }
+}
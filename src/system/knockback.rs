@@ -1,12 +1,15 @@
-use crate::components::{Damaged, Knockback};
+use crate::{
+    components::{Damaged, Hitstop, Knockback},
+    resource::stage::Stage,
+};
 use amethyst::{
-    core::timing::Time,
+    core::Transform,
     ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage},
 };
-use amethyst_sprite_studio::{components::AnimationTime, traits::animation_file::AnimationFile};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
 use std::marker::PhantomData;
 
-// ノックバックの時間管理，ノックバック終了時にクリアする情報のクリアを行う
+// ノックバックの時間管理，押し返し，ノックバック終了時にクリアする情報のクリアを行う
 pub struct KnockbackSystem<T>
 where
     T: AnimationFile,
@@ -30,29 +33,34 @@ where
     T: AnimationFile,
 {
     type SystemData = (
-        Read<'s, Time>,
+        Read<'s, Stage>,
         Entities<'s>,
-        ReadStorage<'s, AnimationTime>,
+        ReadStorage<'s, Hitstop>,
         WriteStorage<'s, Damaged<T>>,
         WriteStorage<'s, Knockback>,
+        WriteStorage<'s, Transform>,
     );
 
     fn run(
         &mut self,
-        (_time, entities, animation_time, mut damaged, mut knockback): Self::SystemData,
+        (stage, entities, hitstops, mut damaged, mut knockback, mut transforms): Self::SystemData,
     ) {
-        #[cfg(not(feature = "count-frame"))]
-        let time = _time.delta_seconds();
-        #[cfg(feature = "count-frame")]
-        let time = 1. / 60.;
-        for (e, animation_time, knockback) in (&*entities, &animation_time, &mut knockback).join() {
-            // ヒットストップがあるので，アニメーション再生中のみノックバックを計算
-            if animation_time.is_play() == false {
-                continue;
-            }
-
+        for (e, knockback, _, transform) in (
+            &*entities,
+            &mut knockback,
+            !&hitstops,
+            (&mut transforms).maybe(),
+        )
+            .join()
+        {
+            // ヒットストップ中はノックバックを進めない
             if knockback.is_knockback() == true {
-                knockback.decrement(time);
+                // 壁より外には押し返さない
+                if let Some(transform) = transform {
+                    let x = stage.clamp(transform.translation().x + knockback.push());
+                    transform.set_translation_x(x);
+                }
+                knockback.decrement();
 
                 if knockback.is_knockback() == false {
                     // ノックバックしないようになったのでダメージ情報をクリア
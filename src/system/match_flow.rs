@@ -0,0 +1,224 @@
+use crate::{
+    components::{
+        ArmorCount, Damaged, Down, Health, HitInfo, HitShake, Hitstop, Jump, Knockback, PlayerTag,
+        Projectile, SkillCount,
+    },
+    resource::match_state::{MatchEvent, MatchPhase, MatchState, RoundResult, WinReason},
+};
+use amethyst::{
+    core::Transform,
+    ecs::{Entities, Join, ReadStorage, System, Write, WriteStorage},
+    shrev::EventChannel,
+};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// ラウンドの進行，勝敗判定，ラウンド開始時のリセットを行う
+pub struct MatchSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> MatchSystem<T> {
+    pub fn new() -> Self {
+        MatchSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for MatchSystem<T>
+where
+    T: AnimationFile,
+{
+    type SystemData = (
+        Write<'s, MatchState>,
+        Write<'s, EventChannel<MatchEvent>>,
+        Entities<'s>,
+        ReadStorage<'s, PlayerTag>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Damaged<T>>,
+        WriteStorage<'s, Knockback>,
+        WriteStorage<'s, SkillCount<T>>,
+        WriteStorage<'s, HitInfo<T>>,
+        WriteStorage<'s, Down>,
+        WriteStorage<'s, ArmorCount<T>>,
+        WriteStorage<'s, HitShake>,
+        WriteStorage<'s, Hitstop>,
+        WriteStorage<'s, Jump>,
+        ReadStorage<'s, Projectile>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut state,
+            mut channel,
+            entities,
+            tags,
+            mut healths,
+            mut transforms,
+            mut damaged,
+            mut knockbacks,
+            mut skill_counts,
+            mut hit_infos,
+            mut downs,
+            mut armor_counts,
+            mut hit_shakes,
+            mut hitstops,
+            mut jumps,
+            projectiles,
+        ): Self::SystemData,
+    ) {
+        if state.reset_requested == true {
+            state.reset_requested = false;
+            state.round += 1;
+            state.phase = MatchPhase::Intro;
+            state.phase_frame = 0;
+            state.rest_frame = state.rule().round_frames;
+
+            // ラウンド開始状態に戻す
+            for (e, tag, transform, damaged, knockback, skill_count, hit_info) in (
+                &*entities,
+                &tags,
+                (&mut transforms).maybe(),
+                (&mut damaged).maybe(),
+                (&mut knockbacks).maybe(),
+                (&mut skill_counts).maybe(),
+                (&mut hit_infos).maybe(),
+            )
+                .join()
+            {
+                if let (Some(transform), Some(&(x, y))) =
+                    (transform, state.rule().start_positions.get(tag))
+                {
+                    transform.set_translation_x(x);
+                    transform.set_translation_y(y);
+                }
+                if let Some(damaged) = damaged {
+                    damaged.clear();
+                }
+                if let Some(knockback) = knockback {
+                    knockback.set_knockback(0);
+                }
+                if let Some(skill_count) = skill_count {
+                    *skill_count = SkillCount::new();
+                }
+                if let Some(hit_info) = hit_info {
+                    *hit_info = HitInfo::default();
+                }
+                downs.remove(e);
+                armor_counts.remove(e);
+                hit_shakes.remove(e);
+                hitstops.remove(e);
+                jumps.remove(e);
+            }
+            for (_, health) in (&tags, &mut healths).join() {
+                health.refill();
+            }
+            // 残っている弾は消す
+            for (e, _) in (&*entities, &projectiles).join() {
+                if let Err(err) = entities.delete(e) {
+                    log::error!("delete projectile error: {:?}", err);
+                }
+            }
+
+            log::info!("round start: {}", state.round);
+            let round = state.round;
+            channel.single_write(MatchEvent::RoundStart { round });
+        }
+
+        match state.phase {
+            MatchPhase::Idle | MatchPhase::Finished => {}
+            MatchPhase::Intro => {
+                state.phase_frame += 1;
+                if state.phase_frame >= state.rule().intro_frames {
+                    state.phase = MatchPhase::Fight;
+                    state.phase_frame = 0;
+                    let round = state.round;
+                    channel.single_write(MatchEvent::Fight { round });
+                }
+            }
+            MatchPhase::Fight => {
+                state.rest_frame = state.rest_frame.saturating_sub(1);
+                if let Some(result) = judge(&tags, &healths, state.rest_frame == 0) {
+                    log::info!("round end: {} {:?}", state.round, result);
+                    if let RoundResult::Win { winner, .. } = result {
+                        *state.wins.entry(winner).or_insert(0) += 1;
+                    }
+                    state.results.push(result);
+                    state.phase = MatchPhase::Outro;
+                    state.phase_frame = 0;
+                    let round = state.round;
+                    channel.single_write(MatchEvent::RoundEnd { round, result });
+                }
+            }
+            MatchPhase::Outro => {
+                state.phase_frame += 1;
+                if state.phase_frame >= state.rule().outro_frames {
+                    let decided = state
+                        .wins
+                        .values()
+                        .any(|&wins| wins >= state.rule().rounds_to_win);
+                    if decided == true || state.round >= state.rule().max_rounds() {
+                        state.phase = MatchPhase::Finished;
+                        let winner = state.winner();
+                        log::info!("match end: {:?}", winner);
+                        channel.single_write(MatchEvent::MatchEnd { winner });
+                    } else {
+                        // 次のラウンドのリセットは次のフレームの開始時に行う
+                        state.reset_requested = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ラウンドの勝敗判定
+fn judge(
+    tags: &ReadStorage<PlayerTag>,
+    healths: &WriteStorage<Health>,
+    is_timeout: bool,
+) -> Option<RoundResult> {
+    let players = (tags, healths)
+        .join()
+        .map(|(tag, health)| (*tag, health.current(), health.is_dead()))
+        .collect::<Vec<_>>();
+
+    let alive = players
+        .iter()
+        .filter(|(_, _, dead)| *dead == false)
+        .collect::<Vec<_>>();
+    if alive.len() < players.len() {
+        return match alive.as_slice() {
+            [] => Some(RoundResult::DoubleKo),
+            [(winner, _, _)] => Some(RoundResult::Win {
+                winner: *winner,
+                reason: WinReason::Ko,
+            }),
+            // 3人以上の場合は1人になるまで続ける
+            _ => None,
+        };
+    }
+
+    if is_timeout == false {
+        return None;
+    }
+
+    let max = players
+        .iter()
+        .map(|(_, current, _)| *current)
+        .fold(std::f32::MIN, f32::max);
+    let leaders = players
+        .iter()
+        .filter(|(_, current, _)| *current >= max)
+        .collect::<Vec<_>>();
+    match leaders.as_slice() {
+        [(winner, _, _)] => Some(RoundResult::Win {
+            winner: *winner,
+            reason: WinReason::TimeOut,
+        }),
+        _ => Some(RoundResult::Draw),
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    components::{
+        ActiveCommand, CharacterStatus, Direction, Down, Hitstop, Jump, Knockback, SkillSet,
+    },
+    id::{command::Command, pack::AnimationKey},
+    resource::stage::Stage,
+};
+use amethyst::{
+    core::Transform,
+    ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage},
+};
+use amethyst_sprite_studio::{components::PlayAnimationKey, traits::animation_file::AnimationFile};
+use std::marker::PhantomData;
+
+// キャラクター定義の移動性能で歩き，ダッシュ，ジャンプの移動を行う
+// 地上の移動はコマンドに対応する技のアニメーションを再生している間だけ行う
+// ヒットストップ中は動かない
+pub struct MovementSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> MovementSystem<T> {
+    pub fn new() -> Self {
+        MovementSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for MovementSystem<T>
+where
+    T: AnimationFile<AnimationKey = AnimationKey>,
+{
+    type SystemData = (
+        Read<'s, Stage>,
+        Entities<'s>,
+        ReadStorage<'s, CharacterStatus>,
+        ReadStorage<'s, SkillSet>,
+        ReadStorage<'s, ActiveCommand>,
+        ReadStorage<'s, PlayAnimationKey<T>>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, Hitstop>,
+        ReadStorage<'s, Knockback>,
+        ReadStorage<'s, Down>,
+        WriteStorage<'s, Jump>,
+        WriteStorage<'s, Transform>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            stage,
+            entities,
+            statuses,
+            skill_sets,
+            active_commands,
+            keys,
+            directions,
+            hitstops,
+            knockbacks,
+            downs,
+            mut jumps,
+            mut transforms,
+        ): Self::SystemData,
+    ) {
+        let mut finished = vec![];
+        for (e, status, skill_set, key, transform, _) in (
+            &*entities,
+            &statuses,
+            &skill_sets,
+            &keys,
+            &mut transforms,
+            !&hitstops,
+        )
+            .join()
+        {
+            let sign = match directions.get(e).cloned().unwrap_or(Direction::Right) {
+                Direction::Right => 1.,
+                Direction::Left => -1.,
+            };
+            let playing = key.play_key().map(|(_, _, anim)| *anim);
+            let is_playing = |command: Command| {
+                playing.is_some() && skill_set.command_skill(&command) == playing.as_ref()
+            };
+            let (mut x, mut y) = (transform.translation().x, transform.translation().y);
+
+            let jump_skills = [Command::VerticalJump, Command::FrontJump, Command::BackJump];
+            match jumps.get_mut(e) {
+                // 空中ではやられ中も落下を続ける
+                Some(jump) if jump.is_landed() == false => {
+                    let (vx, vy) = jump.advance(status.jump().gravity);
+                    x += vx;
+                    y += vy;
+                    if y <= 0. {
+                        y = 0.;
+                        jump.land();
+                    }
+                }
+                // ジャンプの技が終わったら次のジャンプができる
+                Some(_) => {
+                    if jump_skills.iter().any(|&command| is_playing(command)) == false {
+                        finished.push(e);
+                    }
+                }
+                None => {
+                    let stunned = knockbacks
+                        .get(e)
+                        .map(|knockback| knockback.is_knockback())
+                        .unwrap_or(false)
+                        || downs.get(e).is_some();
+                    if stunned == true {
+                        continue;
+                    }
+
+                    let movement = status.movement();
+                    for &(command, speed) in &[
+                        (Command::Walk, movement.walk_speed),
+                        (Command::Back, -movement.back_walk_speed),
+                        (Command::Dash, movement.dash_speed),
+                        (Command::BackDash, -movement.back_dash_speed),
+                    ] {
+                        if is_playing(command) == true {
+                            x += speed * sign;
+                        }
+                    }
+
+                    // 入力が成立したフレームに跳ぶ
+                    let jump = status.jump();
+                    let jumped = [
+                        (Command::VerticalJump, 0.),
+                        (Command::FrontJump, jump.horizontal_speed),
+                        (Command::BackJump, -jump.horizontal_speed),
+                    ]
+                    .iter()
+                    .find(|(command, _)| {
+                        is_playing(*command)
+                            && active_commands
+                                .get(e)
+                                .map(|active| active.active_commands().any(|c| c == command))
+                                .unwrap_or(false)
+                    })
+                    .map(|&(_, horizontal)| (horizontal * sign, jump.vertical_speed));
+                    if let (Some(velocity), true) = (jumped, y <= 0.) {
+                        log::debug!("jump: {:?} {:?}", e, velocity);
+                        if let Err(err) = jumps.insert(e, Jump::new(velocity)) {
+                            log::error!("insert jump error: {:?}", err);
+                        }
+                    }
+                }
+            }
+
+            // 壁より外には出ない
+            transform.set_translation_x(stage.clamp(x));
+            transform.set_translation_y(y);
+        }
+        for e in finished {
+            jumps.remove(e);
+        }
+    }
+}
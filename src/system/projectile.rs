@@ -0,0 +1,240 @@
+use crate::{
+    components::{Direction, HitInfo, Hitstop, Projectile, SkillCount, SpawnHistory},
+    id::{
+        file::FileId,
+        pack::{AnimationKey, PackKey},
+    },
+    paramater::AnimationParam,
+    resource::{clock::FrameClock, stage::Stage},
+};
+use amethyst::{
+    core::Transform,
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, WriteStorage},
+};
+use amethyst_sprite_studio::{
+    components::{AnimationNodes, AnimationTime, BuildRequireData, Node, PlayAnimationKey},
+    traits::animation_file::AnimationFile,
+};
+use std::marker::PhantomData;
+
+// アニメーションデータの生成情報から弾のエンティティを生成する
+// 生成したエンティティは次のフレームから動作する
+// 生成情報のキーは FightTranslation と同じ型で指定する
+pub struct ProjectileSpawnSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> ProjectileSpawnSystem<T> {
+    pub fn new() -> Self {
+        ProjectileSpawnSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for ProjectileSpawnSystem<T>
+where
+    T: AnimationFile<
+        FileId = FileId,
+        PackKey = PackKey,
+        AnimationKey = AnimationKey,
+        UserData = AnimationParam,
+    >,
+{
+    type SystemData = (
+        Read<'s, FrameClock>,
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, SkillCount<T>>,
+        WriteStorage<'s, SpawnHistory<T>>,
+        BuildRequireData<'s, T>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            clock,
+            entities,
+            lazy,
+            directions,
+            skill_counts,
+            mut histories,
+            (play_time, key, transforms, tint, storage, store),
+        ): Self::SystemData,
+    ) {
+        for (e, play_time, key, transform) in (&*entities, &play_time, &key, &transforms).join() {
+            let (&file, &pack, &anim) = match key.play_key() {
+                Some(key) => key,
+                None => continue,
+            };
+            let spawns = match AnimationNodes::<AnimationParam>::make_node::<T>(
+                play_time,
+                tint.get(e),
+                key.play_key(),
+                transform,
+                transform.global_matrix(),
+                &store,
+                &storage,
+            ) {
+                Some(nodes) => nodes
+                    .nodes()
+                    .filter(|Node { hide, .. }| *hide == false)
+                    .filter_map(|Node { user, .. }| user.as_ref().and_then(|user| user.spawn))
+                    .collect::<Vec<_>>(),
+                None => continue,
+            };
+            if spawns.is_empty() == true {
+                continue;
+            }
+
+            let count = skill_counts
+                .get(e)
+                .map(|count| count.skill_count(&(file, pack, anim)))
+                .unwrap_or(0);
+            let history = match histories.entry(e) {
+                Ok(entry) => entry.or_insert(SpawnHistory::new()),
+                Err(err) => {
+                    log::error!("spawn history error: {:?}", err);
+                    continue;
+                }
+            };
+
+            let direction = directions.get(e).cloned().unwrap_or(Direction::Right);
+            let sign = match direction {
+                Direction::Right => 1.,
+                Direction::Left => -1.,
+            };
+
+            for spawn in spawns {
+                // 同じ技の使用中は1度だけ生成
+                if history.is_spawned(&(file, pack, anim, count), spawn.id) == true {
+                    continue;
+                }
+                history.add((file, pack, anim, count), spawn.id);
+
+                let translation = transform.translation();
+                let mut projectile_transform = Transform::default();
+                projectile_transform.set_translation_xyz(
+                    translation.x + spawn.offset.0 * sign,
+                    translation.y + spawn.offset.1,
+                    translation.z,
+                );
+                *projectile_transform.scale_mut() = *transform.scale();
+
+                let mut projectile_key = PlayAnimationKey::<T>::new(spawn.file.unwrap_or(file));
+                projectile_key.set_pack(spawn.pack.unwrap_or(pack));
+                projectile_key.set_animation(spawn.animation);
+
+                let projectile = Projectile::new(
+                    e,
+                    (clock.frame(), spawn.id),
+                    (spawn.velocity.0 * sign, spawn.velocity.1),
+                    spawn.lifetime,
+                    spawn.hit_count,
+                    spawn.durability,
+                );
+                log::debug!("spawn projectile: {:?} {:?}", e, spawn);
+
+                lazy.create_entity(&entities)
+                    .with(projectile_transform)
+                    .with(direction)
+                    .with(projectile_key)
+                    .with(AnimationTime::new())
+                    .with(SkillCount::<T>::new())
+                    .with(projectile)
+                    .build();
+            }
+        }
+    }
+}
+
+// 弾の移動と消滅を行う
+// ヒット情報の適用後に実行する
+pub struct ProjectileSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> ProjectileSystem<T> {
+    pub fn new() -> Self {
+        ProjectileSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for ProjectileSystem<T>
+where
+    T: AnimationFile,
+{
+    type SystemData = (
+        Read<'s, Stage>,
+        Entities<'s>,
+        ReadStorage<'s, HitInfo<T>>,
+        ReadStorage<'s, Hitstop>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Direction>,
+        WriteStorage<'s, Projectile>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            stage,
+            entities,
+            hits,
+            hitstops,
+            mut transforms,
+            mut directions,
+            mut projectiles,
+        ): Self::SystemData,
+    ) {
+        for (e, projectile, transform, direction, hitstop, hit) in (
+            &*entities,
+            &mut projectiles,
+            &mut transforms,
+            (&mut directions).maybe(),
+            hitstops.maybe(),
+            hits.maybe(),
+        )
+            .join()
+        {
+            if let Some(hit) = hit {
+                // ヒットした数だけ残りヒット数を減らす
+                projectile.hit(hit.damaged_owners().len() as u32);
+
+                // 相殺で耐久値を減らす
+                projectile.clash(hit.durability_damage());
+
+                // 跳ね返されたら向きを反転
+                if let Some(owner) = hit.reflected_by() {
+                    projectile.reflect(owner);
+                    if let Some(direction) = direction {
+                        *direction = match direction {
+                            Direction::Right => Direction::Left,
+                            Direction::Left => Direction::Right,
+                        };
+                    }
+                }
+
+                if hit.absorbed() == true {
+                    projectile.vanish();
+                }
+            }
+
+            // ヒットストップ中は動かない
+            if projectile.is_alive() == true && hitstop.is_none() {
+                let (x, y) = projectile.velocity();
+                transform.append_translation_xyz(x, y, 0.);
+                projectile.decrement();
+            }
+
+            if projectile.is_alive() == false || stage.is_outside(transform.translation().x) {
+                log::debug!("destroy projectile: {:?}", e);
+                if let Err(err) = entities.delete(e) {
+                    log::error!("destroy projectile error: {:?}", err);
+                }
+            }
+        }
+    }
+}
@@ -90,7 +90,7 @@ fn register_collision<'s, T, P>(
         transform, user, ..
     } in nodes.nodes().filter(|Node { hide, .. }| *hide == false)
     {
-        if let Some(param) = P::make_collision_data(e, user.as_ref(), collision_system_data) {
+        if let Some(mut param) = P::make_collision_data(e, user.as_ref(), collision_system_data) {
             let translation = transform.translation();
             let scale = transform.scale();
             log::trace!(
@@ -100,6 +100,7 @@ fn register_collision<'s, T, P>(
                 scale.x,
                 scale.y
             );
+            param.set_bounds((translation.x, translation.y), (scale.x, scale.y));
             collisions.update_aabb((translation.x, translation.y), scale.x, scale.y, param);
         }
     }
@@ -0,0 +1,116 @@
+use crate::{
+    components::HitInfo,
+    resource::{
+        hit_level::{HitLevelStore, HitLevelTable},
+        trade::TradeRule,
+    },
+};
+use amethyst::{
+    assets::AssetStorage,
+    ecs::{Entities, Join, Read, System, WriteStorage},
+};
+use amethyst_sprite_studio::traits::animation_file::AnimationFile;
+use std::marker::PhantomData;
+
+// 同じフレームに互いの攻撃が当たった場合の処理を行う
+// ダメージ判定後，ヒット情報の適用前に実行する
+pub struct TradeSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> TradeSystem<T> {
+    pub fn new() -> Self {
+        TradeSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for TradeSystem<T>
+where
+    T: AnimationFile,
+{
+    type SystemData = (
+        Read<'s, TradeRule>,
+        Read<'s, HitLevelStore>,
+        Read<'s, AssetStorage<HitLevelTable>>,
+        Entities<'s>,
+        WriteStorage<'s, HitInfo<T>>,
+    );
+
+    fn run(&mut self, (rule, hit_levels, tables, entities, mut hits): Self::SystemData) {
+        // 互いに攻撃してきた相手になっている組を探す
+        // エンティティ順に1組1回だけ処理する
+        let trades = (&*entities, &hits)
+            .join()
+            .filter_map(|(e, hit)| {
+                let other = hit.attack_owner()?;
+                let other_hit = hits.get(other)?;
+                if e < other && other_hit.attack_owner() == Some(e) {
+                    Some((e, other))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (e1, e2) in trades {
+            // 各エンティティが受けた攻撃のヒットレベル
+            let level1 = hits.get(e1).and_then(|hit| hit.hit_level());
+            let level2 = hits.get(e2).and_then(|hit| hit.hit_level());
+            log::debug!("trade: {:?} {:?} / {:?} {:?}", e1, level1, e2, level2);
+
+            let (winner, loser) = match (level1, level2) {
+                (Some(level1), Some(level2)) if rule.hit_level_priority == true => {
+                    if level1 < level2 {
+                        (Some(e1), Some(e2))
+                    } else if level1 > level2 {
+                        (Some(e2), Some(e1))
+                    } else {
+                        (None, None)
+                    }
+                }
+                _ => (None, None),
+            };
+
+            if let (Some(winner), Some(loser)) = (winner, loser) {
+                // 負けた側の攻撃は当たらなかったことにする
+                // 勝った側には攻撃した側のヒットストップだけ残す
+                let hitstop = hits
+                    .get(loser)
+                    .and_then(|hit| hit.hit_level())
+                    .map(|level| hit_levels.entry(&tables, level).attacker_hitstop());
+                if let Some(hit) = hits.get_mut(winner) {
+                    hit.cancel_damage(hitstop);
+                }
+                if let Some(hit) = hits.get_mut(loser) {
+                    hit.cancel_attack(winner);
+                }
+                log::debug!("trade winner: {:?}", winner);
+                continue;
+            }
+
+            for e in [e1, e2].iter() {
+                if let Some(hit) = hits.get_mut(*e) {
+                    hit.trade = true;
+                    // ガードした攻撃はカウンターヒットにしない
+                    if rule.counter_hit == true && hit.blocked == false {
+                        // 受けた攻撃のヒットレベルに設定があればそちらを使う
+                        let entry = hit
+                            .hit_level()
+                            .map(|level| hit_levels.entry(&tables, level));
+                        let damage_rate = entry
+                            .and_then(|entry| entry.counter_damage_rate)
+                            .unwrap_or(rule.counter_damage_rate);
+                        let counter_hitstop = entry
+                            .and_then(|entry| entry.counter_hitstop)
+                            .unwrap_or(rule.counter_hitstop);
+                        hit.counter_hit = true;
+                        hit.damage = hit.damage.map(|damage| damage * damage_rate);
+                        hit.hitstop = hit.hitstop.map(|hitstop| hitstop + counter_hitstop);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    binding::FightBindings,
+    components::{Direction, Down, Gauge, Health, Knockback, PlayerTag},
+    input::{InputFlag, InputQueue, QueueSource, ScriptedSource},
+    resource::{
+        input_source::{InputSources, SourceOwner},
+        random::{GameRandom, RandomStream},
+        training::{DummyGuard, DummyStance, RecordState, TrainingDummyState, TrainingSetting},
+    },
+    types::random::XorShift,
+};
+use amethyst::{
+    ecs::{Join, Read, ReadStorage, System, Write, WriteStorage},
+    input::InputHandler,
+};
+
+// やられ状態から戻ってこのフレーム数経過したら連続技が途切れたとみなす
+const COMBO_RESET_FRAME: usize = 30;
+
+// トレーニングダミーの入力を生成する
+// 入力の反映より前に実行する
+// システムは入力元の差し替えだけを持ち，ダミーの状態は TrainingDummyState に置く
+pub struct TrainingDummySystem {
+    queue: Option<InputQueue>,
+    controller_locked: bool, // 記録中は操作側の入力を止める
+}
+
+impl TrainingDummySystem {
+    pub fn new() -> Self {
+        TrainingDummySystem {
+            queue: None,
+            controller_locked: false,
+        }
+    }
+}
+
+impl TrainingDummyState {
+    // 設定とやられ状態から右向き基準の入力を決める
+    fn behaviour(
+        &mut self,
+        setting: &TrainingSetting,
+        rng: &mut XorShift,
+        is_knockback: bool,
+    ) -> InputFlag {
+        if is_knockback == true && self.was_knockback == false {
+            // 攻撃を受けた
+            self.combo_guard = true;
+            self.random_guard = rng.chance(setting.guard_random_rate());
+        }
+        if is_knockback == false && self.was_knockback == true {
+            // やられから復帰
+            if setting.reversal().is_empty() == false {
+                self.reversal_frame = Some(0);
+            }
+        }
+        self.was_knockback = is_knockback;
+
+        if is_knockback == false && self.neutral_frame == COMBO_RESET_FRAME {
+            self.combo_guard = false;
+            self.random_guard = rng.chance(setting.guard_random_rate());
+        }
+
+        if let Some(frame) = self.reversal_frame {
+            let reversal = setting.reversal();
+            self.reversal_frame = if frame + 1 < reversal.len() {
+                Some(frame + 1)
+            } else {
+                None
+            };
+            if let Some(&input) = reversal.get(frame) {
+                return input;
+            }
+        }
+
+        let is_guard = match setting.guard() {
+            DummyGuard::None => false,
+            DummyGuard::All => true,
+            DummyGuard::AfterFirstHit => self.combo_guard,
+            DummyGuard::Random => self.random_guard,
+        };
+
+        match (setting.stance(), is_guard) {
+            (DummyStance::Stand, false) => InputFlag::empty(),
+            (DummyStance::Stand, true) => InputFlag::LEFT,
+            (DummyStance::Crouch, false) => InputFlag::DOWN,
+            (DummyStance::Crouch, true) => InputFlag::LEFT_DOWN,
+            (DummyStance::Jump, false) => InputFlag::UP,
+            (DummyStance::Jump, true) => InputFlag::LEFT_UP,
+        }
+    }
+
+    fn playback(&mut self, setting: &mut TrainingSetting, loop_playback: bool) -> InputFlag {
+        if self.playback_frame >= setting.recorded.len() {
+            if loop_playback == true && setting.recorded.is_empty() == false {
+                self.playback_frame = 0;
+            } else {
+                setting.stop_playback();
+                self.playback_frame = 0;
+                return InputFlag::empty();
+            }
+        }
+        let input = setting.recorded[self.playback_frame];
+        self.playback_frame += 1;
+        input
+    }
+}
+
+impl<'s> System<'s> for TrainingDummySystem {
+    type SystemData = (
+        Write<'s, TrainingSetting>,
+        Write<'s, TrainingDummyState>,
+        Write<'s, InputSources>,
+        Write<'s, GameRandom>,
+        Read<'s, InputHandler<FightBindings>>,
+        ReadStorage<'s, PlayerTag>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, Knockback>,
+        ReadStorage<'s, Down>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Gauge>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut setting,
+            mut state,
+            mut sources,
+            mut random,
+            handler,
+            tags,
+            directions,
+            knockbacks,
+            downs,
+            mut healths,
+            mut gauges,
+        ): Self::SystemData,
+    ) {
+        if setting.is_enabled() == false {
+            // トレーニングモード終了時は入力元を戻す
+            if self.queue.take().is_some() {
+                sources.release(setting.dummy(), SourceOwner::Training);
+            }
+            if self.controller_locked == true {
+                sources.release(setting.controller(), SourceOwner::Training);
+                self.controller_locked = false;
+            }
+            return;
+        }
+
+        let queue = match &self.queue {
+            Some(queue) => queue.clone(),
+            None => {
+                let (source, queue) = QueueSource::new();
+                if sources.claim(setting.dummy(), SourceOwner::Training, source) == false {
+                    // CPUなど他のシステムが操作している間は動かさない
+                    log::debug!(
+                        "training dummy is used: {:?}",
+                        sources.owner(setting.dummy())
+                    );
+                    return;
+                }
+                self.queue = Some(queue.clone());
+                state.reset();
+                queue
+            }
+        };
+
+        random.reseed(RandomStream::Training, setting.seed());
+
+        // 記録中は操作側はダミーを動かすので本人は動かさない
+        if setting.is_recording() != self.controller_locked {
+            if setting.is_recording() == true {
+                self.controller_locked = sources.claim(
+                    setting.controller(),
+                    SourceOwner::Training,
+                    ScriptedSource::new(vec![]),
+                );
+            } else {
+                sources.release(setting.controller(), SourceOwner::Training);
+                self.controller_locked = false;
+            }
+        }
+
+        let mut direction = Direction::Right;
+        let mut dummy_knockback = false;
+        let mut controller_knockback = false;
+        for (tag, dir, knockback, down) in
+            (&tags, directions.maybe(), knockbacks.maybe(), downs.maybe()).join()
+        {
+            // ダウン中は起き上がるまでやられ状態として扱う
+            let is_knockback =
+                knockback.map(|k| k.is_knockback()).unwrap_or(false) || down.is_some();
+            if *tag == setting.dummy() {
+                direction = dir.cloned().unwrap_or(Direction::Right);
+                dummy_knockback = is_knockback;
+            } else if *tag == setting.controller() {
+                controller_knockback = is_knockback;
+            }
+        }
+
+        // 入力は右向き基準で扱うので向きに合わせて反転
+        let to_screen = |input: InputFlag| match direction {
+            Direction::Right => input,
+            Direction::Left => input.mirrored(),
+        };
+
+        let input = match setting.record_state {
+            RecordState::Recording => {
+                let input = crate::input::device_input(&handler, setting.controller());
+                setting.recorded.push(to_screen(input));
+                input
+            }
+            RecordState::Playback { loop_playback } => {
+                to_screen(state.playback(&mut setting, loop_playback))
+            }
+            RecordState::Idle => {
+                let rng = random.get_mut(RandomStream::Training);
+                to_screen(state.behaviour(&setting, rng, dummy_knockback))
+            }
+        };
+
+        // 1フレームに1入力
+        queue.clear();
+        queue.push(input);
+
+        // 体力，ゲージの回復
+        if dummy_knockback == false && controller_knockback == false {
+            state.neutral_frame += 1;
+        } else {
+            state.neutral_frame = 0;
+        }
+        if setting.health_refill() == true && state.neutral_frame == setting.refill_delay().max(1) {
+            for (_, health) in (&tags, &mut healths).join() {
+                health.refill();
+            }
+        }
+        if setting.gauge_refill() == true {
+            for (_, gauge) in (&tags, &mut gauges).join() {
+                gauge.refill();
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    components::{HitInfo, Knockback, PlayerTag},
+    flag::Cancel,
+    paramater::AnimationParam,
+    resource::{
+        clock::FrameClock,
+        training_hud::{Exchange, TrainingHud},
+    },
+};
+use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, Write};
+use amethyst_sprite_studio::{
+    components::{AnimationNodes, BuildRequireData, Node},
+    traits::animation_file::AnimationFile,
+};
+use std::marker::PhantomData;
+
+// トレーニング表示用の情報を集計する
+// ヒット情報の適用後に実行する
+pub struct TrainingHudSystem<T> {
+    _animation_file: PhantomData<T>,
+}
+
+impl<T> TrainingHudSystem<T> {
+    pub fn new() -> Self {
+        TrainingHudSystem {
+            _animation_file: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> System<'s> for TrainingHudSystem<T>
+where
+    T: AnimationFile<UserData = AnimationParam> + std::fmt::Debug,
+{
+    type SystemData = (
+        Read<'s, FrameClock>,
+        Write<'s, TrainingHud>,
+        Entities<'s>,
+        ReadStorage<'s, PlayerTag>,
+        ReadStorage<'s, HitInfo<T>>,
+        ReadStorage<'s, Knockback>,
+        BuildRequireData<'s, T>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            clock,
+            mut hud,
+            entities,
+            tags,
+            hits,
+            knockbacks,
+            (play_time, key, transforms, tint, storage, store),
+        ): Self::SystemData,
+    ) {
+        let mut new_exchange = None;
+
+        for (e, &tag, play_time, key, transform) in
+            (&*entities, &tags, &play_time, &key, &transforms).join()
+        {
+            // 表示中のパーツに設定されたキャンセルをまとめる
+            let cancel = AnimationNodes::<T::UserData>::make_node::<T>(
+                play_time,
+                tint.get(e),
+                key.play_key(),
+                transform,
+                transform.global_matrix(),
+                &store,
+                &storage,
+            )
+            .map(|nodes| {
+                nodes
+                    .nodes()
+                    .filter(|Node { hide, .. }| *hide == false)
+                    .filter_map(|Node { user, .. }| user.as_ref().map(|user| user.cancel))
+                    .fold(Cancel::empty(), |acc, cancel| acc | cancel)
+            })
+            .unwrap_or(Cancel::empty());
+
+            let (is_knockback, is_blockstun, hitstun) = knockbacks
+                .get(e)
+                .map(|k| (k.is_knockback(), k.is_blockstun(), k.knockback_frame()))
+                .unwrap_or((false, false, 0));
+
+            let player = hud.players.entry(tag).or_default();
+            player.cancel = cancel;
+            player.hitstun = hitstun;
+            player.actionable = is_knockback == false && cancel.contains(Cancel::STANCE);
+
+            let damage = hits.get(e).and_then(|hit| {
+                Some((
+                    hit.damage()?,
+                    hit.blocked(),
+                    hit.hit_level(),
+                    hit.attack_owner(),
+                ))
+            });
+            match damage {
+                Some((damage, blocked, hit_level, attack_owner)) => {
+                    if blocked == true {
+                        // ガードされた攻撃はコンボに数えない
+                        player.in_combo = false;
+                    } else if player.in_combo == true {
+                        player.combo_count += 1;
+                        player.combo_damage += damage;
+                    } else {
+                        player.in_combo = true;
+                        player.combo_count = 1;
+                        player.combo_damage = damage;
+                    }
+                    player.last_damage = Some(damage);
+                    player.last_hit_level = hit_level;
+
+                    if let Some(&attacker) = attack_owner.and_then(|owner| tags.get(owner)) {
+                        new_exchange = Some(Exchange {
+                            attacker,
+                            defender: tag,
+                            attacker_free: None,
+                            defender_free: None,
+                        });
+                    }
+                }
+                None => {
+                    // ガード硬直中はコンボが途切れている
+                    if is_knockback == false || is_blockstun == true {
+                        player.in_combo = false;
+                    }
+                }
+            }
+        }
+
+        // ヒットするたびに計測しなおす
+        if new_exchange.is_some() {
+            hud.exchange = new_exchange;
+        }
+
+        let frame = clock.frame();
+        let TrainingHud { players, exchange } = &mut *hud;
+        if let Some(current) = exchange {
+            let actionable = |tag| players.get(&tag).map(|p| p.actionable).unwrap_or(true);
+            if current.attacker_free.is_none() && actionable(current.attacker) {
+                current.attacker_free = Some(frame);
+            }
+            if current.defender_free.is_none() && actionable(current.defender) {
+                current.defender_free = Some(frame);
+            }
+
+            if let (Some(attacker_free), Some(defender_free)) =
+                (current.attacker_free, current.defender_free)
+            {
+                let advantage = defender_free as isize - attacker_free as isize;
+                log::debug!("frame advantage: {:?} {:+}", current.attacker, advantage);
+                if let Some(player) = players.get_mut(&current.attacker) {
+                    player.frame_advantage = Some(advantage);
+                }
+                if let Some(player) = players.get_mut(&current.defender) {
+                    player.frame_advantage = Some(-advantage);
+                }
+                *exchange = None;
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod extrude_filter;
+mod input_source;
 mod param_from_data;
 mod update_hit_info;
 
 pub(crate) use extrude_filter::ExtrudeFilter;
+pub use input_source::InputSource;
 pub use param_from_data::ParamaterFromData;
-pub(crate) use update_hit_info::{HitType, UpdateHitInfo, UpdateHitInfoType};
+pub(crate) use update_hit_info::{HitType, UnexpectedHitType, UpdateHitInfo, UpdateHitInfoType};
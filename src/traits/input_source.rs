@@ -0,0 +1,10 @@
+use crate::{binding::FightBindings, components::PlayerTag, input::InputFlag};
+use amethyst::input::InputHandler;
+
+// プレイヤーごとの入力元
+// デバイス，リプレイ，スクリプト，AI，通信相手などから押下中の入力を生成する
+pub trait InputSource: 'static + Send + Sync {
+    // 今フレームに押下中の入力を返す
+    // 押下，離しの判定は前フレームとの差分で行うのでここでは不要
+    fn next_input(&mut self, tag: PlayerTag, handler: &InputHandler<FightBindings>) -> InputFlag;
+}
@@ -8,6 +8,10 @@ pub trait ParamaterFromData<'s, T>: Sized {
         data: Option<&T>,
         system_data: &Self::SystemData,
     ) -> Option<Self>;
+
+    // 判定の範囲(中心座標, 大きさ)を設定する
+    // 範囲を使わないパラメータは何もしない
+    fn set_bounds(&mut self, _position: (f32, f32), _size: (f32, f32)) {}
 }
 
 impl<'s, T> ParamaterFromData<'s, T> for () {
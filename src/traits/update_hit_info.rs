@@ -1,8 +1,21 @@
 use amethyst::ecs::{Component, Entity, SystemData};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HitType {
     Attack,
     Damage,
+    Clash,  // 弾同士の相殺など双方が影響を受ける
+    Ignore, // 接触しても何もしない
+}
+
+// 想定していない判定の組み合わせ
+#[derive(Debug, Clone)]
+pub struct UnexpectedHitType(pub String);
+
+impl std::fmt::Display for UnexpectedHitType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "hit type undefined: {}", self.0)
+    }
 }
 
 // ヒット情報更新に関連する情報
@@ -10,7 +23,11 @@ pub trait UpdateHitInfoType: 'static + Send + Sync + Default + Component {
     type Paramater; // 更新に必要なパラメータ
     type CancelInfo; // ヒット情報更新時に他のエンティティの情報をキャンセルするための情報
 
-    fn check_hit_type(param1: &Self::Paramater, param2: &Self::Paramater) -> HitType;
+    // 想定していない組み合わせはエラーを返し，ヒット情報の更新を行わない
+    fn check_hit_type(
+        param1: &Self::Paramater,
+        param2: &Self::Paramater,
+    ) -> Result<HitType, UnexpectedHitType>;
 }
 
 // ダメージ，攻撃ヒット時の情報を更新する
@@ -35,6 +52,16 @@ pub trait UpdateHitInfo<'s>: UpdateHitInfoType {
         data: &Self::SystemData,
     ) -> Vec<(Entity, Self::CancelInfo)>;
 
+    // 相殺など双方が影響を受ける接触時の更新．
+    // 接触した双方それぞれについて呼び出す
+    fn clash_update(
+        &mut self,
+        other: Entity,
+        param: &Self::Paramater,
+        other_param: &Self::Paramater,
+        data: &Self::SystemData,
+    ) -> Vec<(Entity, Self::CancelInfo)>;
+
     // 他エンティティの更新時にキャンセルされた場合に呼び出す．
     fn cancel(&mut self, targeted: Entity, cancel_info: Self::CancelInfo);
 }
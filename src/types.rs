@@ -3,6 +3,10 @@ mod change_key;
 mod damage_collision_id;
 #[cfg(feature = "debug")]
 pub mod debug;
+pub mod frame_data;
+pub mod random;
+mod spark;
 
 pub(crate) use change_key::ChangeKey;
-pub(crate) use damage_collision_id::DamageCollisionId;
+pub use damage_collision_id::DamageCollisionId;
+pub use spark::SparkKind;
@@ -1,81 +1,302 @@
-use crate::paramater::{CollisionType, FightTranslation};
+use crate::{
+    flag::Cancel,
+    paramater::{AnimationParam, CollisionType, HitLevel},
+    resource::hit_level::{HitLevelEntry, HitLevelTable},
+};
 use amethyst_sprite_studio::{
     resource::data::AnimationData, traits::animation_file::AnimationFile,
 };
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+
+// インスタンスを辿る最大の深さ(循環参照対策)
+const MAX_INSTANCE_DEPTH: usize = 8;
+
+// 攻撃判定のヒット時の情報
+#[derive(Debug, Clone, Copy)]
+pub struct HitFrameInfo {
+    pub hit_level: HitLevel,
+    pub hitstun: usize,           // ヒット硬直(0なら設定表の値)
+    pub blockstun: Option<usize>, // ガード硬直(指定がないか0なら設定表の値)
+}
 
-type PackKey = <FightTranslation as AnimationFile>::PackKey;
-type AnimationKey = <FightTranslation as AnimationFile>::AnimationKey;
+// 1ヒット分の攻撃判定
+// collision_count ごとに別のヒットとして扱う
+#[derive(Debug, Clone)]
+pub struct HitFrames {
+    pub collision_count: u32,
+    pub frames: Vec<(usize, usize)>, // 判定が存在するフレーム
+    pub info: HitFrameInfo,
+}
+
+impl HitFrames {
+    pub fn start(&self) -> usize {
+        self.frames.first().map(|&(start, _)| start).unwrap_or(0)
+    }
+}
 
 // アニメーションをパースして技の情報を取得する
 #[derive(Debug)]
 pub struct SkillInfomation {
-    attack_frames: Vec<(usize, usize)>, // 本体付随の攻撃判定が存在するフレーム
+    total_frame: usize,
+    attack_frames: Vec<(usize, usize)>, // 攻撃判定が存在するフレーム(全ヒット合算)
+    hits: Vec<HitFrames>,               // ヒットごとの判定
+    cancel_frames: Vec<(usize, usize, Cancel)>, // キャンセル可能なフレームと内容
 }
 
 impl SkillInfomation {
-    pub fn make_info(
-        animation: &AnimationData<FightTranslation>,
-        pack: PackKey,
-        anim: AnimationKey,
-    ) -> Option<Self> {
-        let pack = animation.pack(&pack)?;
-        let parts_num = pack.parts().count();
-        let animation = pack.animation(&anim)?;
-        let mut attack_frame_set = BTreeSet::new();
-        for f in 0..animation.total_frame() {
-            for p in 0..parts_num {
-                if animation.hide(p, f) == true {
-                    continue;
-                }
-                if let Some(user) = animation.user(p, f) {
-                    match &user.collision_type {
-                        &Some(CollisionType::Blow { .. })
-                        | &Some(CollisionType::Projectile { .. }) => {
-                            attack_frame_set.insert(f);
-                        }
-                        _ => {}
+    pub fn make_info<T>(
+        animation: &AnimationData<T>,
+        pack: T::PackKey,
+        anim: T::AnimationKey,
+    ) -> Option<Self>
+    where
+        T: AnimationFile<UserData = AnimationParam>,
+    {
+        let total_frame = animation.pack(&pack)?.animation(&anim)?.total_frame();
+        let mut collected = CollectedFrames::default();
+        collect_frames(animation, &pack, &anim, 0, total_frame, &mut collected, 0);
+        let CollectedFrames {
+            hits: hit_frames,
+            cancels,
+        } = collected;
+
+        let attack_frames = frame_ranges(
+            hit_frames
+                .values()
+                .flat_map(|(frames, _)| frames.iter().cloned())
+                .collect::<BTreeSet<_>>(),
+        );
+        let mut hits = hit_frames
+            .into_iter()
+            .map(|(collision_count, (frames, info))| HitFrames {
+                collision_count,
+                frames: frame_ranges(frames),
+                info,
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by_key(|hit| (hit.start(), hit.collision_count));
+        let cancel_frames = cancel_ranges(cancels);
+
+        log::debug!("collisions: {:?}", hits);
+
+        Some(SkillInfomation {
+            total_frame,
+            attack_frames,
+            hits,
+            cancel_frames,
+        })
+    }
+
+    pub fn total_frame(&self) -> usize {
+        self.total_frame
+    }
+
+    pub fn attack_frames(&self) -> &[(usize, usize)] {
+        &self.attack_frames
+    }
+
+    pub fn hits(&self) -> &[HitFrames] {
+        &self.hits
+    }
+
+    // 最初のヒットの情報
+    pub fn hit_info(&self) -> Option<&HitFrameInfo> {
+        self.hits.first().map(|hit| &hit.info)
+    }
+
+    pub fn cancel_frames(&self) -> &[(usize, usize, Cancel)] {
+        &self.cancel_frames
+    }
+
+    // 発生フレーム(攻撃判定が出るフレーム，1始まり)
+    pub fn startup(&self) -> Option<usize> {
+        self.attack_frames.first().map(|&(start, _)| start + 1)
+    }
+
+    // 持続フレーム(最初の攻撃判定から最後の攻撃判定まで)
+    pub fn active(&self) -> Option<usize> {
+        let &(start, _) = self.attack_frames.first()?;
+        let &(_, end) = self.attack_frames.last()?;
+        Some(end - start + 1)
+    }
+
+    // 硬直フレーム(最後の攻撃判定以降)
+    pub fn recovery(&self) -> Option<usize> {
+        let &(_, end) = self.attack_frames.last()?;
+        Some(self.total_frame.saturating_sub(end + 1))
+    }
+
+    // ヒットストップ，硬直はヒットレベルの設定表を参照する(None なら既定値)
+    pub fn hitstop(&self, table: Option<&HitLevelTable>) -> Option<usize> {
+        self.hit_info()
+            .map(|hit| HitLevelEntry::resolve(table, hit.hit_level).defender_hitstop)
+    }
+
+    // 最終段が発生フレームでヒットした場合の硬直差
+    pub fn hit_advantage(&self, table: Option<&HitLevelTable>) -> Option<isize> {
+        let hit = self.hits.last()?;
+        let entry = HitLevelEntry::resolve(table, hit.info.hit_level);
+        let stun = entry.hitstun_frame(hit.info.hitstun);
+        Some(self.advantage(hit.start(), stun, &entry))
+    }
+
+    // 最終段が発生フレームでガードされた場合の硬直差
+    pub fn guard_advantage(&self, table: Option<&HitLevelTable>) -> Option<isize> {
+        let hit = self.hits.last()?;
+        let entry = HitLevelEntry::resolve(table, hit.info.hit_level);
+        let stun = entry.blockstun_frame(hit.info.blockstun)?;
+        Some(self.advantage(hit.start(), stun, &entry))
+    }
+
+    fn advantage(&self, start: usize, stun: usize, entry: &HitLevelEntry) -> isize {
+        // 受けた側のヒットストップが長い分だけ有利になる
+        let hitstop = entry.defender_hitstop as isize - entry.attacker_hitstop() as isize;
+        let rest = self.total_frame.saturating_sub(start + 1);
+        stun as isize - rest as isize + hitstop
+    }
+}
+
+#[derive(Default)]
+struct CollectedFrames {
+    hits: BTreeMap<u32, (BTreeSet<usize>, HitFrameInfo)>, // collision_count ごとの判定
+    cancels: BTreeMap<usize, Cancel>,
+}
+
+// アニメーション内の攻撃判定とキャンセルを集める
+// インスタンスパーツは表示されている間，参照先のアニメーションを先頭から等速で再生しているとみなす
+fn collect_frames<T>(
+    animation: &AnimationData<T>,
+    pack: &T::PackKey,
+    anim: &T::AnimationKey,
+    offset: usize, // 親アニメーションでの開始フレーム
+    limit: usize,  // 親アニメーションで表示されているフレーム数
+    collected: &mut CollectedFrames,
+    depth: usize,
+) where
+    T: AnimationFile<UserData = AnimationParam>,
+{
+    if depth > MAX_INSTANCE_DEPTH {
+        log::warn!("instance too deep: {:?} {:?}", pack, anim);
+        return;
+    }
+    let pack_data = match animation.pack(pack) {
+        Some(pack_data) => pack_data,
+        None => return,
+    };
+    let anim_data = match pack_data.animation(anim) {
+        Some(anim_data) => anim_data,
+        None => return,
+    };
+    let frame_num = anim_data.total_frame().min(limit);
+
+    for (p, part) in pack_data.parts().enumerate() {
+        let visible = (0..frame_num)
+            .filter(|&f| anim_data.hide(p, f) == false)
+            .collect::<Vec<_>>();
+
+        for &f in &visible {
+            if let Some(user) = anim_data.user(p, f) {
+                match &user.collision_type {
+                    &Some(CollisionType::Blow {
+                        hit_level,
+                        ground,
+                        guard,
+                        collision_count,
+                        ..
+                    })
+                    | &Some(CollisionType::Projectile {
+                        hit_level,
+                        ground,
+                        guard,
+                        collision_count,
+                        ..
+                    }) => {
+                        let (frames, _) =
+                            collected.hits.entry(collision_count).or_insert_with(|| {
+                                (
+                                    BTreeSet::new(),
+                                    HitFrameInfo {
+                                        hit_level,
+                                        hitstun: ground.frame(),
+                                        blockstun: guard.map(|guard| guard.frame()),
+                                    },
+                                )
+                            });
+                        frames.insert(offset + f);
                     }
+                    _ => {}
+                }
+                // キャンセルは本体のアニメーションのものだけを見る
+                if depth == 0 && user.cancel.is_empty() == false {
+                    *collected
+                        .cancels
+                        .entry(offset + f)
+                        .or_insert(Cancel::empty()) |= user.cancel;
                 }
             }
         }
 
-        log::info!("test: collisions: {:?}", attack_frame_set);
-
-        let mut attack_frames = vec![];
-        let mut start = None;
-        let mut end = None;
-        // 連続するフレームを取り出す
-        // 0,1,2,3 => (0,3)
-        // 0,1,2,5,6,7 => (0,2), (5,7)
-        for f in attack_frame_set {
-            if start.is_none() == true {
-                start = Some(f);
-            }
-            match end {
-                Some(end_frame) => {
-                    if f - end_frame == 1 {
-                        // 間は空いてないので終端はまだ
-                        log::info!("set end: {}", f);
-                        end = Some(f);
-                    } else {
-                        // 間が空いているので一旦区切る
-                        attack_frames.push((start.unwrap(), end_frame));
-                        start = None;
-                        end = None;
-                    }
-                }
-                None => end = Some(f),
-            }
+        // インスタンスパーツは参照先のアニメーションの判定も含める
+        if let (Some((ref_pack, ref_anim)), Some(&first), Some(&last)) =
+            (part.refference_animation(), visible.first(), visible.last())
+        {
+            collect_frames(
+                animation,
+                ref_pack,
+                ref_anim,
+                offset + first,
+                last - first + 1,
+                collected,
+                depth + 1,
+            );
         }
-        // まだセットしてないフレームが有る場合はそれをセット
-        match (start, end) {
-            (Some(start), Some(end)) => {
-                attack_frames.push((start, end));
+    }
+}
+
+// 連続するフレームを取り出す
+// 0,1,2,3 => (0,3)
+// 0,1,2,5,6,7 => (0,2), (5,7)
+fn frame_ranges<I>(frames: I) -> Vec<(usize, usize)>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut ranges = vec![];
+    let mut current: Option<(usize, usize)> = None;
+    for f in frames {
+        current = match current {
+            // 間は空いてないので終端はまだ
+            Some((start, end)) if f == end + 1 => Some((start, f)),
+            // 間が空いているので一旦区切る
+            Some(range) => {
+                ranges.push(range);
+                Some((f, f))
             }
-            _ => {}
-        }
+            None => Some((f, f)),
+        };
+    }
+    // まだセットしてないフレームが有る場合はそれをセット
+    if let Some(range) = current {
+        ranges.push(range);
+    }
+    ranges
+}
 
-        Some(SkillInfomation { attack_frames })
+// 同じキャンセル内容が連続するフレームを取り出す
+fn cancel_ranges(cancels: BTreeMap<usize, Cancel>) -> Vec<(usize, usize, Cancel)> {
+    let mut ranges = vec![];
+    let mut current: Option<(usize, usize, Cancel)> = None;
+    for (f, cancel) in cancels {
+        current = match current {
+            Some((start, end, c)) if f == end + 1 && c == cancel => Some((start, f, c)),
+            Some(range) => {
+                ranges.push(range);
+                Some((f, f, cancel))
+            }
+            None => Some((f, f, cancel)),
+        };
+    }
+    if let Some(range) = current {
+        ranges.push(range);
     }
+    ranges
 }
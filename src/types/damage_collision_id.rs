@@ -45,6 +45,18 @@ where
             animation_count,
         }
     }
+
+    pub(crate) fn collision_owner(&self) -> Entity {
+        self.collision_owner
+    }
+
+    // スナップショットから作り直したエンティティへの参照を付け替える
+    pub(crate) fn remap_owner<F>(&mut self, remap: F)
+    where
+        F: Fn(Entity) -> Entity,
+    {
+        self.collision_owner = remap(self.collision_owner);
+    }
 }
 
 impl<T> PartialOrd for DamageCollisionId<T>
@@ -1,10 +1,11 @@
 use crate::{
-    components::{Damaged, Knockback},
+    components::{Damaged, Knockback, PlayerTag},
     paramater::FightTranslation,
+    resource::training_hud::TrainingHud,
 };
 use amethyst::{
     core::{math::Point2, Transform},
-    ecs::{Entity, ReadStorage},
+    ecs::{Entity, Read, ReadStorage},
     renderer::{debug_drawing::DebugLinesComponent, palette::rgb::Srgba},
 };
 use amethyst_sprite_studio::components::{AnimationTime, PlayAnimationKey};
@@ -20,11 +21,13 @@ impl<'s> DebugDisplayFormat<'s> for DisplayInfo {
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Damaged<FightTranslation>>,
         ReadStorage<'s, Knockback>,
+        ReadStorage<'s, PlayerTag>,
+        Read<'s, TrainingHud>,
     );
 
     fn display(
         e: Entity,
-        (time, key, transform, damaged, knockback): &Self::DisplayData,
+        (time, key, transform, damaged, knockback, tag, hud): &Self::DisplayData,
     ) -> Option<String> {
         let mut out = Vec::new();
         let time = time.get(e)?;
@@ -56,7 +59,24 @@ impl<'s> DebugDisplayFormat<'s> for DisplayInfo {
         ));
 
         if let Some(knockback) = knockback.get(e) {
-            out.push(format!("Knockback: {:.4} secs", knockback.knockback_time()));
+            out.push(format!("Knockback: {:3} F", knockback.knockback_frame()));
+        }
+
+        if let Some(player) = tag.get(e).and_then(|tag| hud.player(*tag)) {
+            if let Some(advantage) = player.frame_advantage() {
+                out.push(format!("Advantage: {:+} F", advantage));
+            }
+            if let (Some(damage), Some(level)) = (player.last_damage(), player.last_hit_level()) {
+                out.push(format!("Hit: {:.1} ({:?})", damage, level));
+            }
+            if player.combo_count() > 0 {
+                out.push(format!(
+                    "Combo: {} hit, {:.1}",
+                    player.combo_count(),
+                    player.combo_damage()
+                ));
+            }
+            out.push(format!("Cancel: {:?}", player.cancel()));
         }
 
         if let Some(damaged) = damaged.get(e) {
@@ -71,7 +91,7 @@ impl<'s> DebugDisplayFormat<'s> for DisplayInfo {
     fn debug_lines(
         e: Entity,
         debug_lines: &mut DebugLinesComponent,
-        (_, _, transform, _, knockback): &Self::DisplayData,
+        (_, _, transform, _, knockback, _, _): &Self::DisplayData,
         position_z: f32,
     ) -> Option<()> {
         let transform = transform.get(e)?;
@@ -84,7 +104,8 @@ impl<'s> DebugDisplayFormat<'s> for DisplayInfo {
                 let color = Srgba::new(1., 0., 1., 1.);
 
                 let left_top = Point2::new(base_x, base_y + 10.);
-                let right_down = Point2::new(base_x + knockback.knockback_time() * 600., base_y);
+                let right_down =
+                    Point2::new(base_x + knockback.knockback_frame() as f32 * 10., base_y);
 
                 debug_lines.add_rectangle_2d(left_top, right_down, position_z, color);
             }
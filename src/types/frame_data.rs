@@ -0,0 +1,172 @@
+use crate::{
+    id::pack::{AnimationKey, PackKey},
+    paramater::FightTranslation,
+    resource::hit_level::HitLevelTable,
+    types::analyze::SkillInfomation,
+};
+use amethyst_sprite_studio::resource::data::AnimationData;
+use serde::Serialize;
+
+// フレームデータ表の1行
+#[derive(Serialize, Debug)]
+pub struct FrameDataRow {
+    pub pack: PackKey,
+    pub animation: AnimationKey,
+    pub total: usize,
+    pub startup: Option<usize>,
+    pub active: Option<usize>,
+    pub recovery: Option<usize>,
+    pub hitstop: Option<usize>,
+    pub hit_advantage: Option<isize>,
+    pub guard_advantage: Option<isize>,
+    pub hits: Vec<String>,   // "開始-終了:ヒットレベル"
+    pub cancel: Vec<String>, // "開始-終了:キャンセル内容"
+}
+
+impl FrameDataRow {
+    fn new(
+        pack: PackKey,
+        animation: AnimationKey,
+        info: &SkillInfomation,
+        table: Option<&HitLevelTable>,
+    ) -> Self {
+        FrameDataRow {
+            pack,
+            animation,
+            total: info.total_frame(),
+            startup: info.startup(),
+            active: info.active(),
+            recovery: info.recovery(),
+            hitstop: info.hitstop(table),
+            hit_advantage: info.hit_advantage(table),
+            guard_advantage: info.guard_advantage(table),
+            hits: info
+                .hits()
+                .iter()
+                .map(|hit| {
+                    let frames = hit
+                        .frames
+                        .iter()
+                        .map(|(start, end)| format!("{}-{}", start + 1, end + 1))
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    format!("{}:{:?}", frames, hit.info.hit_level)
+                })
+                .collect(),
+            cancel: info
+                .cancel_frames()
+                .iter()
+                .map(|(start, end, cancel)| format!("{}-{}:{:?}", start + 1, end + 1, cancel))
+                .collect(),
+        }
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            format!("{:?}", self.pack),
+            format!("{:?}", self.animation),
+            self.total.to_string(),
+            optional(self.startup),
+            optional(self.active),
+            optional(self.recovery),
+            optional(self.hitstop),
+            advantage(self.hit_advantage),
+            advantage(self.guard_advantage),
+            self.hits.join(" "),
+            self.cancel.join(" "),
+        ]
+    }
+}
+
+const HEADERS: [&str; 11] = [
+    "pack",
+    "animation",
+    "total",
+    "startup",
+    "active",
+    "recovery",
+    "hitstop",
+    "hit_advantage",
+    "guard_advantage",
+    "hits",
+    "cancel",
+];
+
+fn optional<V: ToString>(value: Option<V>) -> String {
+    value.map(|v| v.to_string()).unwrap_or("-".into())
+}
+
+fn advantage(value: Option<isize>) -> String {
+    value.map(|v| format!("{:+}", v)).unwrap_or("-".into())
+}
+
+// 全パック・全アニメーションのフレームデータ表
+#[derive(Serialize, Debug, Default)]
+pub struct FrameDataTable {
+    rows: Vec<FrameDataRow>,
+}
+
+impl FrameDataTable {
+    // ヒットレベルの設定表がなければ既定値で計算する
+    pub fn from_animation(
+        animation: &AnimationData<FightTranslation>,
+        table: Option<&HitLevelTable>,
+    ) -> Self {
+        let mut rows = vec![];
+        for &pack in PackKey::all() {
+            for &anim in AnimationKey::all() {
+                if let Some(info) = SkillInfomation::make_info(animation, pack, anim) {
+                    rows.push(FrameDataRow::new(pack, anim, &info, table));
+                }
+            }
+        }
+        FrameDataTable { rows }
+    }
+
+    pub fn rows(&self) -> &[FrameDataRow] {
+        &self.rows
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = HEADERS.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let line = row
+                .columns()
+                .into_iter()
+                .map(|column| {
+                    if column.contains(',') || column.contains('"') {
+                        format!("\"{}\"", column.replace('"', "\"\""))
+                    } else {
+                        column
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push_str(&line);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("| {} |\n", HEADERS.join(" | "));
+        markdown.push_str(&format!(
+            "|{}\n",
+            HEADERS.iter().map(|_| " --- |").collect::<String>()
+        ));
+        for row in &self.rows {
+            let columns = row
+                .columns()
+                .into_iter()
+                .map(|column| column.replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            markdown.push_str(&format!("| {} |\n", columns.join(" | ")));
+        }
+        markdown
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.rows)
+    }
+}
@@ -0,0 +1,45 @@
+// シード指定で再現できる乱数(xorshift64)
+// ゲーム進行に使う乱数はロールバック，リプレイで同じ結果になるようにこれを使う
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        // 0 だと常に 0 になるので適当な値にする
+        let state = if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        };
+        XorShift { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    // 0 以上 max 未満
+    pub fn next_range(&mut self, max: u64) -> u64 {
+        if max == 0 {
+            return 0;
+        }
+        self.next_u64() % max
+    }
+
+    // 0.0 以上 1.0 未満
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    // 確率 rate で true
+    pub fn chance(&mut self, rate: f32) -> bool {
+        self.next_f32() < rate
+    }
+}
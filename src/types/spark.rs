@@ -0,0 +1,24 @@
+use crate::paramater::HitLevel;
+
+// ヒットエフェクトの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SparkKind {
+    Light,  // ヒットレベル1
+    Medium, // ヒットレベル2
+    Heavy,  // ヒットレベル3以上
+    Guard,  // ガードされた
+    Clash,  // 攻撃同士の相殺
+}
+
+impl SparkKind {
+    pub fn from_hit(hit_level: HitLevel, blocked: bool) -> Self {
+        if blocked == true {
+            return SparkKind::Guard;
+        }
+        match hit_level.level() {
+            0..=1 => SparkKind::Light,
+            2 => SparkKind::Medium,
+            _ => SparkKind::Heavy,
+        }
+    }
+}
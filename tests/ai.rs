@@ -0,0 +1,89 @@
+mod common;
+
+use amethyst::{
+    core::Transform,
+    ecs::{Builder, World, WorldExt},
+    utils::circular_buffer::CircularBuffer,
+};
+use amethyst_sprite_studio::components::PlayAnimationKey;
+use fight_game::{
+    bundle::FightAiBundle,
+    components::{Direction, PlayerTag},
+    headless::HeadlessWorld,
+    id::file::FileId,
+    input::{InputFlag, InputSignal},
+    paramater::FightTranslation,
+    resource::ai::{AiConfig, AiDifficulty, AiSetting},
+    snapshot::GameSnapshot,
+};
+use std::collections::BTreeMap;
+
+const FRAMES: usize = 120;
+const SAVE_FRAME: usize = 60;
+
+fn build<'a, 'b>(seed: u64) -> HeadlessWorld<'a, 'b> {
+    let mut world = common::builder()
+        .with_bundle(FightAiBundle::<FightTranslation>::new())
+        .expect("ai bundle")
+        .build();
+    create_player(world.world_mut(), PlayerTag::P1, -100., Direction::Right);
+    create_player(world.world_mut(), PlayerTag::P2, 100., Direction::Left);
+    world.script_inputs(PlayerTag::P1, vec![]);
+    world
+        .world_mut()
+        .write_resource::<AiSetting>()
+        .set_ai(PlayerTag::P2, AiConfig::new(AiDifficulty::Hard, seed));
+    world
+}
+
+fn create_player(world: &mut World, tag: PlayerTag, x: f32, direction: Direction) {
+    let mut transform = Transform::default();
+    transform.set_translation_x(x);
+    world
+        .create_entity()
+        .with(tag)
+        .with(transform)
+        .with(direction)
+        .with(PlayAnimationKey::<FightTranslation>::new(FileId::Sample))
+        .build();
+}
+
+// 1フレームずつ進めてCPUの入力を集める
+fn ai_inputs(world: &mut HeadlessWorld, frames: usize) -> Vec<InputFlag> {
+    (0..frames)
+        .map(|_| {
+            world.step();
+            world
+                .world()
+                .read_resource::<CircularBuffer<BTreeMap<PlayerTag, InputSignal>>>()
+                .queue()
+                .back()
+                .and_then(|signal| signal.get(&PlayerTag::P2))
+                .map(|signal| signal.is_down_flag())
+                .unwrap_or(InputFlag::empty())
+        })
+        .collect()
+}
+
+#[test]
+fn same_seed_produces_the_same_inputs() {
+    let mut world1 = build(7);
+    let mut world2 = build(7);
+
+    let inputs1 = ai_inputs(&mut world1, FRAMES);
+    let inputs2 = ai_inputs(&mut world2, FRAMES);
+    assert!(inputs1.iter().any(|input| input.is_empty() == false));
+    assert_eq!(inputs1, inputs2);
+}
+
+#[test]
+fn restored_snapshot_replays_the_same_inputs() {
+    let mut world = build(7);
+    ai_inputs(&mut world, SAVE_FRAME);
+    let saved = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+
+    let expected = ai_inputs(&mut world, FRAMES - SAVE_FRAME);
+    saved.restore(world.world_mut());
+    let replayed = ai_inputs(&mut world, FRAMES - SAVE_FRAME);
+    assert_eq!(replayed, expected);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use amethyst::{
+    assets::{AssetStorage, ProgressCounter},
+    ecs::{Entity, WorldExt},
+};
+use amethyst_sprite_studio::components::PlayAnimationKey;
+use fight_game::{
+    components::{CharacterStatus, Direction, Health, PlayerTag},
+    headless::HeadlessWorld,
+    id::pack::AnimationKey,
+    load::CharacterLoad,
+    paramater::FightTranslation,
+    prefab::CharacterPrefab,
+    resource::character::{CharacterDefinition, CharacterStore},
+};
+
+const LOAD_FRAMES: usize = 600;
+
+fn build<'a, 'b>() -> HeadlessWorld<'a, 'b> {
+    common::builder()
+        .with_loader(env!("CARGO_MANIFEST_DIR"))
+        .build()
+}
+
+// サンプルのキャラクター定義を読み込んで生成する
+fn spawn_sample(world: &mut HeadlessWorld) -> Entity {
+    let mut progress = ProgressCounter::new();
+    world
+        .world_mut()
+        .load_character("tests/assets", "sample", &mut progress);
+    let loaded = world.run_until(LOAD_FRAMES, |world| {
+        let store = world.read_resource::<CharacterStore>();
+        let storage = world.read_resource::<AssetStorage<CharacterDefinition>>();
+        store
+            .character("sample")
+            .and_then(|handle| storage.get(handle))
+            .is_some()
+    });
+    assert!(loaded, "character not loaded");
+
+    CharacterPrefab::new("sample", PlayerTag::P1, (0., 0.), Direction::Right)
+        .spawn(world.world_mut())
+        .expect("spawn character")
+}
+
+fn set_animation(world: &mut HeadlessWorld, e: Entity, animation: AnimationKey) {
+    world
+        .world_mut()
+        .write_storage::<PlayAnimationKey<FightTranslation>>()
+        .get_mut(e)
+        .expect("animation key")
+        .set_animation(animation);
+}
+
+#[test]
+fn character_definition_loads_and_spawns() {
+    let mut world = build();
+    let e = spawn_sample(&mut world);
+
+    let statuses = world.world().read_storage::<CharacterStatus>();
+    let status = statuses.get(e).expect("character status");
+    assert_eq!(status.name(), "sample");
+    assert_eq!(status.movement().walk_speed, 3.);
+    assert_eq!(status.jump().vertical_speed, 16.);
+
+    let healths = world.world().read_storage::<Health>();
+    assert_eq!(healths.get(e).map(|health| health.max()), Some(1000.));
+}
+
+#[test]
+fn walk_skill_moves_by_the_character_walk_speed() {
+    let mut world = build();
+    let e = spawn_sample(&mut world);
+
+    set_animation(&mut world, e, AnimationKey::Walk);
+    world.run(2);
+    assert_eq!(world.position(e), Some((6., 0.)));
+
+    // 後ろ歩きは後ろ歩きの速度で下がる
+    set_animation(&mut world, e, AnimationKey::Back);
+    world.run(2);
+    assert_eq!(world.position(e), Some((1., 0.)));
+}
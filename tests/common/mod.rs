@@ -0,0 +1,25 @@
+// 各テストで共通の戦闘シミュレーションの構築
+#![allow(dead_code)]
+
+use fight_game::{
+    components::HitInfo,
+    headless::{HeadlessBuilder, HeadlessWorld},
+    paramater::{CollisionParamater, FightTranslation},
+};
+
+// 格ゲー用のバンドルを登録したビルダー
+// ローダーや追加のバンドルはこの後に登録する
+pub fn builder<'a, 'b>() -> HeadlessBuilder<'a, 'b> {
+    HeadlessBuilder::new()
+        .expect("headless builder")
+        .with_fight_bundles::<
+            FightTranslation,
+            CollisionParamater<FightTranslation>,
+            HitInfo<FightTranslation>,
+        >()
+        .expect("fight bundles")
+}
+
+pub fn build<'a, 'b>() -> HeadlessWorld<'a, 'b> {
+    builder().build()
+}
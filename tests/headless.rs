@@ -0,0 +1,72 @@
+mod common;
+
+use amethyst::ecs::{Builder, WorldExt};
+use fight_game::{
+    components::{Direction, PlayerTag},
+    input::InputFlag,
+    resource::{clock::FrameClock, input_history::InputHistory},
+};
+
+#[test]
+fn step_advances_one_simulation_frame() {
+    let mut world = common::build();
+    assert_eq!(world.frame(), 0);
+
+    world.step();
+    assert_eq!(world.frame(), 1);
+
+    world.run(29);
+    assert_eq!(world.frame(), 30);
+}
+
+#[test]
+fn scripted_inputs_are_consumed_one_per_frame() {
+    let mut world = common::build();
+    world.script_inputs(PlayerTag::P1, vec![InputFlag::RIGHT; 5]);
+    world.script_inputs(PlayerTag::P2, vec![]);
+
+    world.run(8);
+
+    let history = world.world().read_resource::<InputHistory>();
+    let p1 = history
+        .entries(PlayerTag::P1)
+        .map(|entry| (entry.input(), entry.frames()))
+        .collect::<Vec<_>>();
+    assert_eq!(p1, vec![(InputFlag::RIGHT, 5), (InputFlag::empty(), 3)]);
+
+    let p2 = history
+        .entries(PlayerTag::P2)
+        .map(|entry| (entry.input(), entry.frames()))
+        .collect::<Vec<_>>();
+    assert_eq!(p2, vec![(InputFlag::empty(), 8)]);
+}
+
+#[test]
+fn run_until_stops_when_predicate_holds() {
+    let mut world = common::build();
+    let reached = world.run_until(100, |world| {
+        world.read_resource::<FrameClock>().frame() >= 10
+    });
+    assert!(reached);
+    assert_eq!(world.frame(), 10);
+}
+
+#[test]
+fn input_history_notation_follows_the_player_direction() {
+    let mut world = common::build();
+    world
+        .world_mut()
+        .create_entity()
+        .with(PlayerTag::P1)
+        .with(Direction::Left)
+        .build();
+    world.script_inputs(PlayerTag::P1, vec![InputFlag::LEFT; 3]);
+    world.script_inputs(PlayerTag::P2, vec![InputFlag::LEFT; 3]);
+
+    world.run(3);
+
+    // 左向きで画面左に入れているので前入力
+    let history = world.world().read_resource::<InputHistory>();
+    assert_eq!(history.format(PlayerTag::P1), "6 x3");
+    assert_eq!(history.format(PlayerTag::P2), "4 x3");
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use amethyst::{
+    core::Transform,
+    ecs::{Builder, World, WorldExt},
+};
+use amethyst_sprite_studio::components::PlayAnimationKey;
+use fight_game::{
+    components::{Direction, Health, PlayerTag},
+    headless::HeadlessWorld,
+    id::file::FileId,
+    input::InputFlag,
+    netplay::{NetplayConfig, NetplayEvent, NetplaySession},
+    paramater::FightTranslation,
+    resource::match_state::MatchState,
+    snapshot::GameSnapshot,
+};
+use std::net::{SocketAddr, UdpSocket};
+
+const INPUT_FRAMES: u64 = 60;
+const SETTLE_FRAMES: u64 = 30;
+const MAX_RETRY: usize = 1000;
+
+fn build<'a, 'b>() -> HeadlessWorld<'a, 'b> {
+    let mut world = common::build();
+    create_player(world.world_mut(), PlayerTag::P1, -100.);
+    create_player(world.world_mut(), PlayerTag::P2, 100.);
+    world.world_mut().write_resource::<MatchState>().start();
+    world
+}
+
+fn create_player(world: &mut World, tag: PlayerTag, x: f32) {
+    let mut transform = Transform::default();
+    transform.set_translation_x(x);
+    world
+        .create_entity()
+        .with(tag)
+        .with(transform)
+        .with(Direction::Right)
+        .with(Health::new(1000.))
+        .with(PlayAnimationKey::<FightTranslation>::new(FileId::Sample))
+        .build();
+}
+
+// 空いているポートを探す
+fn free_addr() -> SocketAddr {
+    UdpSocket::bind("127.0.0.1:0")
+        .and_then(|socket| socket.local_addr())
+        .expect("free port")
+}
+
+// 毎フレーム入力を変えて相手の予測を外させる
+fn input(tag: PlayerTag, frame: u64) -> InputFlag {
+    if frame >= INPUT_FRAMES {
+        return InputFlag::empty();
+    }
+    match (tag, frame % 3) {
+        (PlayerTag::P1, 0) => InputFlag::RIGHT,
+        (PlayerTag::P1, 1) => InputFlag::A,
+        (PlayerTag::P2, 2) => InputFlag::LEFT,
+        (PlayerTag::P2, 0) => InputFlag::B,
+        _ => InputFlag::empty(),
+    }
+}
+
+struct Peer<'a, 'b> {
+    tag: PlayerTag,
+    world: HeadlessWorld<'a, 'b>,
+    session: NetplaySession<FightTranslation>,
+    events: Vec<NetplayEvent>,
+}
+
+impl<'a, 'b> Peer<'a, 'b> {
+    fn new(local: PlayerTag, remote: PlayerTag, addr: SocketAddr, peer: SocketAddr) -> Self {
+        let config = NetplayConfig {
+            local,
+            remote,
+            input_delay: 0,
+            ..Default::default()
+        };
+        let mut world = build();
+        let mut session = NetplaySession::bind(addr, peer, config).expect("bind");
+        session.start(world.world_mut());
+        Peer {
+            tag: local,
+            world,
+            session,
+            events: vec![],
+        }
+    }
+
+    fn advance(&mut self) -> bool {
+        let frame = self.session.frame();
+        let (world, simulation) = self.world.split_mut();
+        let advanced = self
+            .session
+            .advance(world, simulation, input(self.tag, frame))
+            .expect("advance");
+        self.events.extend(self.session.drain_events());
+        advanced
+    }
+}
+
+#[test]
+fn loopback_sessions_stay_in_sync_after_rollback() {
+    let (addr1, addr2) = (free_addr(), free_addr());
+    let mut p1 = Peer::new(PlayerTag::P1, PlayerTag::P2, addr1, addr2);
+    let mut p2 = Peer::new(PlayerTag::P2, PlayerTag::P1, addr2, addr1);
+
+    let target = INPUT_FRAMES + SETTLE_FRAMES;
+    let mut retry = 0;
+    while p1.session.frame() < target || p2.session.frame() < target {
+        // P1 を先に進めて P2 の入力を予測させる
+        let advanced1 = p1.session.frame() < target && p1.advance();
+        let advanced2 = p2.session.frame() < target && p2.advance();
+        if advanced1 == false && advanced2 == false {
+            retry += 1;
+            assert!(retry < MAX_RETRY, "sessions stalled");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    }
+
+    let rollbacks = p1
+        .events
+        .iter()
+        .chain(p2.events.iter())
+        .filter(|event| match event {
+            NetplayEvent::Rollback { .. } => true,
+            _ => false,
+        })
+        .count();
+    assert!(rollbacks > 0, "no rollback happened");
+
+    let desyncs = p1
+        .events
+        .iter()
+        .chain(p2.events.iter())
+        .filter(|event| match event {
+            NetplayEvent::Desync { .. } => true,
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+    assert!(desyncs.is_empty(), "desync: {:?}", desyncs);
+
+    // 入力が揃った後の状態が一致する
+    let snapshot1 = GameSnapshot::<FightTranslation>::capture(p1.world.world_mut());
+    let snapshot2 = GameSnapshot::<FightTranslation>::capture(p2.world.world_mut());
+    assert_eq!(snapshot1.frame(), target);
+    assert_eq!(snapshot2.frame(), target);
+    assert_eq!(snapshot1.checksum(), snapshot2.checksum());
+}
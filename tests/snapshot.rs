@@ -0,0 +1,143 @@
+mod common;
+
+use amethyst::{
+    core::Transform,
+    ecs::{Builder, Entity, Join, World, WorldExt},
+};
+use amethyst_sprite_studio::components::PlayAnimationKey;
+use fight_game::{
+    components::{Direction, Health, PlayerTag},
+    headless::HeadlessWorld,
+    id::file::FileId,
+    input::InputFlag,
+    paramater::FightTranslation,
+    resource::{
+        input_history::InputHistory,
+        match_state::{MatchPhase, MatchState},
+    },
+    snapshot::GameSnapshot,
+};
+
+const FRAMES: usize = 40;
+const SAVE_FRAME: usize = 10;
+
+fn create_entity(world: &mut World, tag: Option<PlayerTag>, x: f32, health: f32) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation_x(x);
+    let builder = world
+        .create_entity()
+        .with(transform)
+        .with(Direction::Right)
+        .with(Health::new(health))
+        .with(PlayAnimationKey::<FightTranslation>::new(FileId::Sample));
+    match tag {
+        Some(tag) => builder.with(tag).build(),
+        None => builder.build(),
+    }
+}
+
+fn inputs(tag: PlayerTag) -> Vec<InputFlag> {
+    (0..FRAMES)
+        .map(|frame| match (tag, frame % 7) {
+            (PlayerTag::P1, 0) => InputFlag::A,
+            (PlayerTag::P1, _) => InputFlag::RIGHT,
+            (PlayerTag::P2, 3) => InputFlag::LEFT_DOWN,
+            (PlayerTag::P2, _) => InputFlag::empty(),
+        })
+        .collect()
+}
+
+fn script_from(world: &mut HeadlessWorld, frame: usize) {
+    for &tag in &[PlayerTag::P1, PlayerTag::P2] {
+        world.script_inputs(tag, inputs(tag)[frame..].to_vec());
+    }
+}
+
+fn health_values(world: &World) -> Vec<f32> {
+    let healths = world.read_storage::<Health>();
+    let keys = world.read_storage::<PlayAnimationKey<FightTranslation>>();
+    let mut values = (&healths, &keys)
+        .join()
+        .map(|(health, _)| health.max())
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values
+}
+
+#[test]
+fn restore_and_resimulate_reaches_the_same_checksum() {
+    let mut world = common::build();
+    create_entity(world.world_mut(), Some(PlayerTag::P1), -100., 1000.);
+    create_entity(world.world_mut(), Some(PlayerTag::P2), 100., 1000.);
+    world.world_mut().write_resource::<MatchState>().start();
+    script_from(&mut world, 0);
+
+    world.run(SAVE_FRAME);
+    let saved = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+    let saved_phase = world.world().read_resource::<MatchState>().phase();
+
+    world.run(FRAMES - SAVE_FRAME);
+    let expected = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+
+    saved.restore(world.world_mut());
+    assert_eq!(world.frame(), SAVE_FRAME as u64);
+    assert_eq!(
+        world.world().read_resource::<MatchState>().phase(),
+        saved_phase
+    );
+    let restored = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+    assert_eq!(restored.checksum(), saved.checksum());
+
+    script_from(&mut world, SAVE_FRAME);
+    world.run(FRAMES - SAVE_FRAME);
+    let resimulated = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+    assert_eq!(resimulated.frame(), expected.frame());
+    assert_eq!(resimulated.checksum(), expected.checksum());
+}
+
+#[test]
+fn restore_undoes_entity_creation_and_deletion() {
+    let mut world = common::build();
+    create_entity(world.world_mut(), Some(PlayerTag::P1), -100., 1000.);
+    create_entity(world.world_mut(), Some(PlayerTag::P2), 100., 1000.);
+    let removed = create_entity(world.world_mut(), None, 0., 10.);
+    world.run(1);
+
+    let saved = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+    assert_eq!(health_values(world.world()), vec![10., 1000., 1000.]);
+
+    // 保存後に1つ消して1つ増やす
+    world
+        .world_mut()
+        .delete_entity(removed)
+        .expect("delete entity");
+    let created = create_entity(world.world_mut(), None, 50., 20.);
+    world.run(1);
+    assert_eq!(health_values(world.world()), vec![20., 1000., 1000.]);
+
+    saved.restore(world.world_mut());
+    assert_eq!(world.world().is_alive(created), false);
+    assert_eq!(health_values(world.world()), vec![10., 1000., 1000.]);
+}
+
+#[test]
+fn resimulation_does_not_count_inputs_twice_in_the_history() {
+    let mut world = common::build();
+    create_entity(world.world_mut(), Some(PlayerTag::P1), -100., 1000.);
+    create_entity(world.world_mut(), Some(PlayerTag::P2), 100., 1000.);
+    script_from(&mut world, 0);
+
+    world.run(SAVE_FRAME);
+    let saved = GameSnapshot::<FightTranslation>::capture(world.world_mut());
+    world.run(FRAMES - SAVE_FRAME);
+    let expected = world
+        .world()
+        .read_resource::<InputHistory>()
+        .format(PlayerTag::P1);
+
+    saved.restore(world.world_mut());
+    script_from(&mut world, SAVE_FRAME);
+    world.run(FRAMES - SAVE_FRAME);
+    let history = world.world().read_resource::<InputHistory>();
+    assert_eq!(history.format(PlayerTag::P1), expected);
+}